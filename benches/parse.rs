@@ -0,0 +1,26 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use time_duration_calculation::{
+    calculate_time_difference_24h, calculate_time_difference_from_range_str, parse_time_components,
+    TimeOfDay,
+};
+
+fn bench_parse(c: &mut Criterion) {
+    c.bench_function("parse_time_components", |b| {
+        b.iter(|| parse_time_components(black_box("10:30:15PM")))
+    });
+    c.bench_function("TimeOfDay::parse", |b| {
+        b.iter(|| TimeOfDay::parse(black_box("10:30:15PM")))
+    });
+}
+
+fn bench_calculate(c: &mut Criterion) {
+    c.bench_function("calculate_time_difference_from_range_str", |b| {
+        b.iter(|| calculate_time_difference_from_range_str(black_box("9:00AM-5:30PM")))
+    });
+    c.bench_function("calculate_time_difference_24h", |b| {
+        b.iter(|| calculate_time_difference_24h(black_box("09:00-17:30")))
+    });
+}
+
+criterion_group!(benches, bench_parse, bench_calculate);
+criterion_main!(benches);
@@ -0,0 +1,123 @@
+use crate::time_difference::span;
+use crate::time_span::TimeSpan;
+use crate::TimeError;
+
+/// Keeps a running total and count of range durations, e.g. while reading ranges one line
+/// at a time, so the total and average can be reported at the end.
+///
+/// ```
+/// use time_duration_calculation::DurationAccumulator;
+///
+/// let mut accumulator = DurationAccumulator::new();
+/// accumulator.push("9:00AM-5:30PM").unwrap();
+/// assert!(accumulator.push("not a range").is_err());
+/// accumulator.push("1:00PM-3:00PM").unwrap();
+/// assert_eq!(accumulator.total_hours(), 10.5);
+/// assert_eq!(accumulator.average_hours(), Some(5.25));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DurationAccumulator {
+    total_seconds: u64,
+    count: u64,
+}
+
+impl DurationAccumulator {
+    /// Creates an accumulator with no ranges added.
+    pub fn new() -> Self {
+        DurationAccumulator::default()
+    }
+
+    /// Adds the duration of `range_str` to the total and returns it in hours.
+    /// An invalid range is returned as an error and leaves the total and count unchanged.
+    pub fn push(&mut self, range_str: &str) -> Result<f64, TimeError> {
+        let span = span(range_str)?;
+        self.add(span);
+        Ok(span.as_hours_f64())
+    }
+
+    /// Adds an already calculated span, e.g. from `calculate_range_allowing_overnight`,
+    /// counting it as one range.
+    pub fn add(&mut self, span: TimeSpan) {
+        self.total_seconds += u64::from(span.total_seconds());
+        self.count += 1;
+    }
+
+    /// Returns the number of ranges added.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the total duration of the ranges added, in whole minutes. Seconds are kept
+    /// in the total, so two 30-second ranges add up to a minute.
+    pub fn total_minutes(&self) -> u64 {
+        self.total_seconds / 60
+    }
+
+    /// Returns the total duration of the ranges added, in hours, to the second.
+    pub fn total_hours(&self) -> f64 {
+        self.total_seconds as f64 / 3600.0
+    }
+
+    /// Returns the mean duration of the ranges added, in hours, or `None` if none were added.
+    pub fn average_hours(&self) -> Option<f64> {
+        (self.count > 0).then(|| self.total_hours() / self.count as f64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_duration_accumulator() {
+        let mut accumulator = DurationAccumulator::new();
+        assert_eq!(accumulator.total_hours(), 0.0);
+        assert_eq!(accumulator.average_hours(), None);
+
+        assert_eq!(accumulator.push("9:00AM-5:30PM"), Ok(8.5));
+        let before_errors = accumulator;
+        assert!(accumulator.push("5:00PM-9:00AM").is_err());
+        assert!(accumulator.push("9:00AM-").is_err());
+        assert_eq!(accumulator, before_errors);
+
+        assert_eq!(accumulator.push("1:00PM-2:30PM"), Ok(1.5));
+        assert_eq!(accumulator.count(), 2);
+        assert_eq!(accumulator.total_minutes(), 600);
+        assert_eq!(accumulator.total_hours(), 10.0);
+        assert_eq!(accumulator.average_hours(), Some(5.0));
+    }
+
+    #[test]
+    fn test_duration_accumulator_many_ranges() {
+        let mut accumulator = DurationAccumulator::new();
+        for _ in 0..60_000 {
+            accumulator.push("12:00AM-11:59PM").unwrap();
+        }
+        assert_eq!(accumulator.total_minutes(), 60_000 * 1439);
+        assert_eq!(accumulator.total_hours(), 60_000.0 * 1439.0 / 60.0);
+        assert_eq!(accumulator.average_hours(), Some(1439.0 / 60.0));
+    }
+
+    #[test]
+    fn test_duration_accumulator_add() {
+        let mut accumulator = DurationAccumulator::new();
+        accumulator.add(TimeSpan::from_minutes(240));
+        accumulator.add(TimeSpan::from_seconds(90));
+        assert_eq!(accumulator.push("9:00AM-10:00AM"), Ok(1.0));
+        assert_eq!(accumulator.count(), 3);
+        assert_eq!(accumulator.total_minutes(), 301);
+        assert_eq!(accumulator.total_hours(), (301.0 * 60.0 + 30.0) / 3600.0);
+    }
+
+    #[test]
+    fn test_duration_accumulator_keeps_seconds() {
+        let mut accumulator = DurationAccumulator::new();
+        let hours = accumulator.push("9:00:00AM-9:00:30AM").unwrap();
+        assert_eq!(hours, 30.0 / 3600.0);
+        assert_eq!(accumulator.total_hours(), hours);
+        assert_eq!(accumulator.total_minutes(), 0);
+        accumulator.push("9:00:00AM-9:00:30AM").unwrap();
+        assert_eq!(accumulator.total_minutes(), 1);
+        assert_eq!(accumulator.average_hours(), Some(hours));
+    }
+}
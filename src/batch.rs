@@ -0,0 +1,115 @@
+use crate::time_difference::{calculate_range, RangeCalculation};
+use crate::TimeError;
+use std::io::BufRead;
+
+/// Calculates each non-blank line of `reader` as a range, pairing the result in hours with
+/// its 1-based line number. Blank lines are skipped but still counted. A line that is not
+/// valid UTF-8 is yielded as `TimeError::ReadFailed` and reading continues with the next
+/// line, while any other read error is yielded the same way and ends the iteration.
+pub fn process_lines<R: BufRead>(
+    reader: R,
+) -> impl Iterator<Item = (usize, Result<f64, TimeError>)> {
+    process_range_lines(reader)
+        .map(|(line_number, result)| (line_number, result.map(|range| range.span.as_hours_f64())))
+}
+
+/// Like `process_lines`, but yields each range's resolved start and end along with its span.
+pub fn process_range_lines<R: BufRead>(
+    reader: R,
+) -> impl Iterator<Item = (usize, Result<RangeCalculation, TimeError>)> {
+    process_range_lines_with(reader, calculate_range)
+}
+
+/// Like `process_range_lines`, but calculates each line with `calculate`, e.g.
+/// `calculate_range_allowing_overnight`.
+pub fn process_range_lines_with<R: BufRead, F>(
+    reader: R,
+    mut calculate: F,
+) -> impl Iterator<Item = (usize, Result<RangeCalculation, TimeError>)>
+where
+    F: FnMut(&str) -> Result<RangeCalculation, TimeError>,
+{
+    let mut read_failed = false;
+    reader
+        .split(b'\n')
+        .enumerate()
+        .map_while(move |(index, bytes)| {
+            if read_failed {
+                return None;
+            }
+            let line_number = index + 1;
+            // Splitting on bytes keeps a line that is not UTF-8 from ending the iteration.
+            let line = match bytes {
+                Ok(bytes) => String::from_utf8(bytes).map_err(|e| e.to_string()),
+                Err(e) => {
+                    read_failed = true;
+                    Err(e.to_string())
+                }
+            };
+            match line {
+                Ok(line) if line.trim().is_empty() => Some(None),
+                Ok(line) => Some(Some((line_number, calculate(line.trim())))),
+                Err(message) => Some(Some((line_number, Err(TimeError::ReadFailed { message })))),
+            }
+        })
+        .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_lines() {
+        let input = "9:00AM-5:30PM\n\n  1:00PM-3:00PM  \n5:00PM-9:00AM\n";
+        let results: Vec<(usize, Result<f64, TimeError>)> =
+            process_lines(input.as_bytes()).collect();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], (1, Ok(8.5)));
+        assert_eq!(results[1], (3, Ok(2.0)));
+        assert_eq!(results[2].0, 4);
+        assert!(matches!(
+            results[2].1,
+            Err(TimeError::EndBeforeStart { .. })
+        ));
+    }
+
+    #[test]
+    fn test_process_lines_end_of_day() {
+        let results: Vec<(usize, Result<f64, TimeError>)> =
+            process_lines("22:00-24:00\n00:00-24:00\n".as_bytes()).collect();
+        assert_eq!(results, vec![(1, Ok(2.0)), (2, Ok(24.0))]);
+    }
+
+    #[test]
+    fn test_process_lines_read_error() {
+        let input: &[u8] = b"9:00AM-5:30PM\n\xff\n1:00PM-3:00PM\n";
+        let results: Vec<(usize, Result<f64, TimeError>)> = process_lines(input).collect();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], (1, Ok(8.5)));
+        assert!(matches!(results[1], (2, Err(TimeError::ReadFailed { .. }))));
+        assert_eq!(results[2], (3, Ok(2.0)));
+    }
+
+    #[test]
+    fn test_process_lines_invalid_bytes_mid_line() {
+        let input: &[u8] = b"9:00AM-5:30PM\r\n9:00\xc3\x28AM-5:00PM\r\n\r\n1:00PM-3:00PM";
+        let results: Vec<(usize, Result<f64, TimeError>)> = process_lines(input).collect();
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], (1, Ok(8.5)));
+        assert!(matches!(results[1], (2, Err(TimeError::ReadFailed { .. }))));
+        assert_eq!(results[2], (4, Ok(2.0)));
+    }
+
+    #[test]
+    fn test_process_range_lines_with() {
+        let input = "10:00PM-2:00AM\n9:00AM-5:30PM\n";
+        let spans: Vec<_> = process_range_lines_with(
+            input.as_bytes(),
+            crate::time_difference::calculate_range_allowing_overnight,
+        )
+        .map(|(line_number, result)| (line_number, result.map(|range| range.span.total_minutes())))
+        .collect();
+        assert_eq!(spans, vec![(1, Ok(240)), (2, Ok(510))]);
+    }
+}
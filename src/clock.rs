@@ -0,0 +1,63 @@
+use crate::time_of_day::TimeOfDay;
+use crate::time_range::TimeRange;
+use crate::TimeError;
+use chrono::Timelike;
+
+impl TimeOfDay {
+    /// Returns the current local time of day from the system clock, to the second.
+    pub fn now() -> TimeOfDay {
+        let now = chrono::Local::now();
+        // chrono reports a leap second through the nanoseconds, so the second is at most 59.
+        TimeOfDay::from_hms(now.hour(), now.minute(), now.second())
+            .expect("the system clock gives a valid time of day")
+    }
+}
+
+/// Returns the hours elapsed today from `time_str` until now, e.g. 2.5 for "9:00AM" at
+/// 11:30AM. The time is read like `TimeOfDay::parse`, so one without AM/PM is AM. A time
+/// later than now is `TimeError::EndBeforeStart`; use `signed_time_difference` for a
+/// negative result instead.
+pub fn hours_since(time_str: &str) -> Result<f64, TimeError> {
+    hours_between(TimeOfDay::parse(time_str)?, TimeOfDay::now())
+}
+
+/// Returns the hours from now until `time_str` later today. Like `hours_since`, a time
+/// earlier than now is `TimeError::EndBeforeStart`.
+pub fn hours_until(time_str: &str) -> Result<f64, TimeError> {
+    hours_between(TimeOfDay::now(), TimeOfDay::parse(time_str)?)
+}
+
+/// Calculates the hours from `start` to `end` as the duration of a `TimeRange`.
+fn hours_between(start: TimeOfDay, end: TimeOfDay) -> Result<f64, TimeError> {
+    TimeRange::new(start, end).map(|range| range.duration_hours())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hours_between() {
+        let nine = TimeOfDay::parse("9:00AM").unwrap();
+        let half_eleven = TimeOfDay::parse("11:30:36AM").unwrap();
+        assert_eq!(hours_between(nine, half_eleven), Ok(2.51));
+        assert_eq!(hours_between(nine, nine), Ok(0.0));
+        assert!(matches!(
+            hours_between(half_eleven, nine),
+            Err(TimeError::EndBeforeStart {
+                start_minutes: 690,
+                end_minutes: 540,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_hours_since_and_until() {
+        assert!(hours_since("12:00AM").unwrap() >= 0.0);
+        assert!(hours_until("11:59:59PM").unwrap() >= 0.0);
+        assert!(hours_since("13:00PM").is_err());
+        let now = TimeOfDay::now();
+        assert!(now.hour() < 24);
+    }
+}
@@ -0,0 +1,481 @@
+use crate::time_conversion::{ImplicitMeridians, Meridian};
+use crate::time_difference::{check_input_length, deduct_break, resolve_with, ResolvedRange};
+use crate::time_of_day::TimeOfDay;
+use crate::time_rounding::RoundingMode;
+use crate::time_span::TimeSpan;
+use crate::TimeError;
+use alloc::string::{String, ToString};
+
+/// The longest input, in bytes, that a calculator accepts by default.
+pub const DEFAULT_MAX_INPUT_LENGTH: usize = 64;
+
+/// The span, in seconds, beyond which `smart_meridians` rereads a range with one meridian.
+const SMART_MERIDIAN_THRESHOLD_SECONDS: u32 = 12 * 60 * 60;
+
+/// Calculates range durations with a set of options, configured with builder methods:
+///
+/// ```
+/// use time_duration_calculation::{DurationCalculator, RoundingMode};
+///
+/// let calculator = DurationCalculator::new()
+///     .separator("to")
+///     .break_minutes(30)
+///     .rounding(RoundingMode::NearestQuarter);
+/// assert_eq!(calculator.calculate("9:00AM to 5:40PM"), Ok(8.25));
+/// ```
+///
+/// The break is deducted before rounding, so a rounded result is what gets billed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DurationCalculator {
+    separator: String,
+    allow_overnight: bool,
+    rounding: RoundingMode,
+    break_minutes: u32,
+    implicit_meridians: ImplicitMeridians,
+    same_meridian: bool,
+    smart_meridians: bool,
+    noon_start: bool,
+    lenient: bool,
+    day_start: TimeOfDay,
+    reject_zero_duration: bool,
+    max_input_length: usize,
+}
+
+impl DurationCalculator {
+    /// Creates a calculator with the defaults used by `calculate_time_difference_from_range_str`:
+    /// a '-' separator, no overnight ranges, exact results, no break, 9:00-5:30 read as AM-PM,
+    /// a zero-length range giving 0.0, and inputs limited to `DEFAULT_MAX_INPUT_LENGTH` bytes.
+    pub fn new() -> Self {
+        DurationCalculator {
+            separator: "-".to_string(),
+            allow_overnight: false,
+            rounding: RoundingMode::Exact,
+            break_minutes: 0,
+            implicit_meridians: ImplicitMeridians::default(),
+            same_meridian: false,
+            smart_meridians: false,
+            noon_start: false,
+            lenient: false,
+            day_start: TimeOfDay::MIDNIGHT,
+            reject_zero_duration: false,
+            max_input_length: DEFAULT_MAX_INPUT_LENGTH,
+        }
+    }
+
+    /// Sets the text between the start and end times, e.g. "to".
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.separator = separator.to_string();
+        self
+    }
+
+    /// Treats an end time earlier than the start time as falling on the next day. The
+    /// `day_start` still applies, but since an end before it also falls on the next day, it
+    /// does not change any duration: with a 4:00AM day start, "2:00AM-5:00AM" is 3 hours.
+    pub fn allow_overnight(mut self, allow_overnight: bool) -> Self {
+        self.allow_overnight = allow_overnight;
+        self
+    }
+
+    /// Sets how the result is rounded.
+    pub fn rounding(mut self, rounding: RoundingMode) -> Self {
+        self.rounding = rounding;
+        self
+    }
+
+    /// Sets an unpaid break to deduct from each range.
+    pub fn break_minutes(mut self, break_minutes: u32) -> Self {
+        self.break_minutes = break_minutes;
+        self
+    }
+
+    /// Sets the AM/PM assumed for each end when neither time specifies one.
+    pub fn implicit_meridians(mut self, implicit_meridians: ImplicitMeridians) -> Self {
+        self.implicit_meridians = implicit_meridians;
+        self
+    }
+
+    /// Assumes a time without AM/PM shares the other time's meridian, replacing
+    /// `implicit_meridians`. Such times are first read as AM; if the range then ends before it
+    /// starts, they are read as PM instead. So "2:00-4:00" is 2:00AM-4:00AM and "noon-5:00" is
+    /// 12:00PM-5:00PM. A shared meridian does not change the order of two plain times, so
+    /// "11:00-1:00" ends before it starts either way and is still an error, reported for PM.
+    pub fn same_meridian(mut self, same_meridian: bool) -> Self {
+        self.same_meridian = same_meridian;
+        self
+    }
+
+    /// Rereads a range whose implicit AM/PM gives a span of over 12 hours as if both times
+    /// shared a meridian, as `same_meridian` does, when that gives a shorter positive span. So
+    /// "1:00-4:00" is 3 hours rather than the 15 of 1:00AM-4:00PM, while "9:00-5:30" keeps its
+    /// 8.5. Only ranges missing an AM/PM are affected, and `same_meridian` takes precedence.
+    ///
+    /// This is a guess that short shifts are more common than long ones, so it gets a real
+    /// shift of over 12 hours wrong: "7:00-8:00" meaning 7:00AM-8:00PM becomes 1 hour. It
+    /// cannot tell 1:00AM-4:00AM from 1:00PM-4:00PM, but both give the same duration.
+    pub fn smart_meridians(mut self, smart_meridians: bool) -> Self {
+        self.smart_meridians = smart_meridians;
+        self
+    }
+
+    /// Reads a start of "12:xx" without AM/PM as noon rather than midnight, so "12:00-1:00" is
+    /// 1 hour rather than the 13 of 12:00AM-1:00PM, and "12:30-5:00" is 4.5. A range that would
+    /// then end before it starts keeps the midnight reading, and `same_meridian` takes
+    /// precedence.
+    ///
+    /// A plain "12:00" start almost always means lunchtime, but the default reads every start
+    /// without AM/PM alike, so this is opt-in to keep existing results unchanged.
+    pub fn noon_start(mut self, noon_start: bool) -> Self {
+        self.noon_start = noon_start;
+        self
+    }
+
+    /// Reads times as by `parse_time_lenient`, so "9 am-5 pm", "9AM-5PM" and "9.00-17.30"
+    /// are accepted. A time without minutes still needs AM/PM, so "9-5" is rejected.
+    pub fn lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Sets when the business day starts, e.g. 4:00AM, so that a range ending after midnight
+    /// but before then, such as "11:00PM-2:00AM", is within one day. A range that crosses the
+    /// day start, such as "2:00AM-5:00AM", then ends before it starts. Defaults to midnight.
+    ///
+    /// With `allow_overnight`, such a range instead ends on the next day, so durations are the
+    /// same as with a midnight day start.
+    pub fn day_start(mut self, day_start: TimeOfDay) -> Self {
+        self.day_start = day_start;
+        self
+    }
+
+    /// Rejects a range whose start and end are the same, such as "10:00AM-10:00AM",
+    /// with `TimeError::ZeroDuration` instead of returning 0.0.
+    pub fn reject_zero_duration(mut self, reject_zero_duration: bool) -> Self {
+        self.reject_zero_duration = reject_zero_duration;
+        self
+    }
+
+    /// Sets the longest input, in bytes, that is parsed. Longer inputs are rejected with
+    /// `TimeError::InputTooLong` before any splitting, which bounds the work done on untrusted
+    /// input. The functions that take a range without a calculator use the default limit.
+    pub fn max_input_length(mut self, max_input_length: usize) -> Self {
+        self.max_input_length = max_input_length;
+        self
+    }
+
+    /// Calculates the duration of `range_str` in hours.
+    pub fn calculate(&self, range_str: &str) -> Result<f64, TimeError> {
+        let worked = self.worked_span(range_str)?;
+        Ok(self.rounding.apply(worked).as_hours_f64())
+    }
+
+    /// Checks that `range_str` is a range `calculate` accepts, without calculating its duration.
+    pub fn validate(&self, range_str: &str) -> Result<(), TimeError> {
+        self.worked_span(range_str).map(|_| ())
+    }
+
+    /// Parses and checks `range_str` and returns its span less the break, before rounding.
+    fn worked_span(&self, range_str: &str) -> Result<TimeSpan, TimeError> {
+        check_input_length(range_str, self.max_input_length)?;
+        let range = self.resolve(range_str)?;
+        if self.reject_zero_duration && range.start.seconds_from_midnight() == range.end_seconds() {
+            return Err(TimeError::ZeroDuration {
+                input: range_str.to_string(),
+            });
+        }
+        let span = if self.allow_overnight {
+            range.overnight_span_with_day_start(self.day_start)
+        } else {
+            range.span_with_day_start(self.day_start)?
+        };
+        deduct_break(span, self.break_minutes)
+    }
+
+    fn resolve<'a>(&self, range_str: &'a str) -> Result<ResolvedRange<'a>, TimeError> {
+        if self.same_meridian {
+            return self.resolve_same_meridian(range_str);
+        }
+        let mut range = resolve_with(
+            range_str,
+            &self.separator,
+            self.implicit_meridians,
+            self.lenient,
+        )?;
+        // Only a start of 12:xx read as AM is at hour 0; rereading it as PM leaves a start
+        // that gave its own AM/PM unchanged.
+        if self.noon_start && range.assumed_meridian && range.start.hour() == 0 {
+            let noon = resolve_with(
+                range_str,
+                &self.separator,
+                ImplicitMeridians {
+                    start: Meridian::Pm,
+                    end: self.implicit_meridians.end,
+                },
+                self.lenient,
+            )?;
+            if noon.end_seconds() >= noon.start.seconds_from_midnight() {
+                range = noon;
+            }
+        }
+        let is_long = range.end_seconds()
+            > range.start.seconds_from_midnight() + SMART_MERIDIAN_THRESHOLD_SECONDS;
+        if self.smart_meridians && range.assumed_meridian && is_long {
+            let shared = self.resolve_same_meridian(range_str)?;
+            if shared.end_seconds() > shared.start.seconds_from_midnight() {
+                return Ok(shared);
+            }
+        }
+        Ok(range)
+    }
+
+    /// Resolves a range for `same_meridian`, reading times without AM/PM as AM, or as PM if
+    /// the range then ends before it starts.
+    fn resolve_same_meridian<'a>(
+        &self,
+        range_str: &'a str,
+    ) -> Result<ResolvedRange<'a>, TimeError> {
+        let as_am = resolve_with(
+            range_str,
+            &self.separator,
+            ImplicitMeridians {
+                start: Meridian::Am,
+                end: Meridian::Am,
+            },
+            self.lenient,
+        )?;
+        if as_am.assumed_meridian && as_am.end_seconds() < as_am.start.seconds_from_midnight() {
+            return resolve_with(
+                range_str,
+                &self.separator,
+                ImplicitMeridians {
+                    start: Meridian::Pm,
+                    end: Meridian::Pm,
+                },
+                self.lenient,
+            );
+        }
+        Ok(as_am)
+    }
+}
+
+impl Default for DurationCalculator {
+    fn default() -> Self {
+        DurationCalculator::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn test_duration_calculator_defaults() {
+        let calculator = DurationCalculator::default();
+        assert_eq!(calculator, DurationCalculator::new());
+        assert_eq!(calculator.calculate("9:00-5:30"), Ok(8.5));
+        assert!(calculator.calculate("10:00PM-2:00AM").is_err());
+    }
+
+    #[test]
+    fn test_duration_calculator_options() {
+        let calculator = DurationCalculator::new()
+            .separator("to")
+            .allow_overnight(true)
+            .break_minutes(30)
+            .rounding(RoundingMode::CeilQuarter);
+        assert_eq!(calculator.calculate("10:00PM to 6:10AM"), Ok(7.75));
+        assert!(matches!(
+            calculator.calculate("9:00AM to 9:20AM"),
+            Err(TimeError::BreakExceedsSpan { .. })
+        ));
+
+        let pm_pm = DurationCalculator::new().implicit_meridians(ImplicitMeridians {
+            start: Meridian::Pm,
+            end: Meridian::Pm,
+        });
+        assert_eq!(pm_pm.calculate("1:00-4:00"), Ok(3.0));
+    }
+
+    #[test]
+    fn test_duration_calculator_reject_zero_duration() {
+        assert_eq!(
+            DurationCalculator::new().calculate("10:00AM-10:00AM"),
+            Ok(0.0)
+        );
+        let calculator = DurationCalculator::new().reject_zero_duration(true);
+        assert_eq!(
+            calculator.calculate("10:00AM-10:00AM"),
+            Err(TimeError::ZeroDuration {
+                input: "10:00AM-10:00AM".to_string()
+            })
+        );
+        assert!(calculator
+            .clone()
+            .allow_overnight(true)
+            .calculate("10:00PM-10:00PM")
+            .is_err());
+        assert_eq!(calculator.calculate("10:00AM-10:01AM"), Ok(1.0 / 60.0));
+    }
+
+    #[test]
+    fn test_duration_calculator_max_input_length() {
+        let oversized = ":".repeat(1 << 20);
+        assert_eq!(
+            DurationCalculator::new().calculate(&oversized),
+            Err(TimeError::InputTooLong {
+                length: 1 << 20,
+                max_length: DEFAULT_MAX_INPUT_LENGTH,
+            })
+        );
+        let padded = format!("9:00AM-5:30PM{}", " ".repeat(60));
+        assert!(matches!(
+            DurationCalculator::new().calculate(&padded),
+            Err(TimeError::InputTooLong { length: 73, .. })
+        ));
+        assert_eq!(
+            DurationCalculator::new()
+                .max_input_length(80)
+                .calculate(&padded),
+            Ok(8.5)
+        );
+        assert!(DurationCalculator::new()
+            .max_input_length(5)
+            .calculate("9:00AM-5:30PM")
+            .is_err());
+    }
+
+    #[test]
+    fn test_duration_calculator_same_meridian() {
+        let calculator = DurationCalculator::new().same_meridian(true);
+        assert_eq!(calculator.calculate("2:00-4:00"), Ok(2.0));
+        assert_eq!(calculator.calculate("noon-5:00"), Ok(5.0));
+        assert_eq!(calculator.calculate("9:00AM-5:00PM"), Ok(8.0));
+        assert!(matches!(
+            calculator.calculate("11:00-1:00"),
+            Err(TimeError::EndBeforeStart { start_interpreted, .. }) if start_interpreted == "11:00PM"
+        ));
+        assert_eq!(DurationCalculator::new().calculate("2:00-4:00"), Ok(14.0));
+    }
+
+    #[test]
+    fn test_duration_calculator_validate() {
+        let calculator = DurationCalculator::new();
+        assert_eq!(calculator.validate("9:00AM-5:30PM"), Ok(()));
+        assert_eq!(
+            calculator.validate("5:30PM-9:00AM"),
+            calculator.calculate("5:30PM-9:00AM").map(|_| ())
+        );
+        assert!(calculator.validate("9:00AM-5:30").is_err());
+        assert!(calculator
+            .clone()
+            .break_minutes(60)
+            .validate("9:00AM-9:30AM")
+            .is_err());
+        assert_eq!(
+            calculator.allow_overnight(true).validate("5:30PM-9:00AM"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_duration_calculator_day_start() {
+        let four_am = TimeOfDay::from_hms(4, 0, 0).unwrap();
+        let calculator = DurationCalculator::new().day_start(four_am);
+        assert_eq!(calculator.calculate("11:00PM-2:00AM"), Ok(3.0));
+        assert_eq!(calculator.calculate("9:00AM-5:30PM"), Ok(8.5));
+        assert_eq!(calculator.calculate("4:00AM-3:59AM"), Ok(1439.0 / 60.0));
+        assert!(matches!(
+            calculator.calculate("2:00AM-5:00AM"),
+            Err(TimeError::EndBeforeStart { .. })
+        ));
+        assert!(DurationCalculator::new()
+            .calculate("11:00PM-2:00AM")
+            .is_err());
+        assert_eq!(
+            DurationCalculator::new().calculate("2:00AM-5:00AM"),
+            Ok(3.0)
+        );
+    }
+
+    #[test]
+    fn test_duration_calculator_day_start_overnight() {
+        let four_am = TimeOfDay::from_hms(4, 0, 0).unwrap();
+        let calculator = DurationCalculator::new()
+            .day_start(four_am)
+            .allow_overnight(true);
+        let overnight = DurationCalculator::new().allow_overnight(true);
+        for range in [
+            "11:00PM-2:00AM",
+            "2:00AM-5:00AM",
+            "5:00AM-3:00AM",
+            "02:00-24:00",
+        ] {
+            assert_eq!(
+                calculator.calculate(range),
+                overnight.calculate(range),
+                "{}",
+                range
+            );
+        }
+        assert_eq!(calculator.calculate("2:00AM-5:00AM"), Ok(3.0));
+        assert_eq!(calculator.calculate("02:00-24:00"), Ok(22.0));
+        assert_eq!(calculator.calculate("5:00AM-3:00AM"), Ok(22.0));
+    }
+
+    #[test]
+    fn test_duration_calculator_lenient() {
+        let calculator = DurationCalculator::new().lenient(true);
+        assert_eq!(calculator.calculate("9AM-5PM"), Ok(8.0));
+        assert_eq!(calculator.calculate("9 am - 5:30 pm"), Ok(8.5));
+        assert_eq!(calculator.calculate("9a-5p"), Ok(8.0));
+        assert_eq!(calculator.calculate("9.00-17.30"), Ok(8.5));
+        assert_eq!(calculator.calculate("9:00-5:30"), Ok(8.5));
+        assert!(calculator.calculate("9-5").is_err());
+        assert!(calculator.calculate("9AM-5").is_err());
+
+        let strict = DurationCalculator::new();
+        assert!(strict.calculate("9AM-5PM").is_err());
+        assert!(strict.calculate("9.00-17.30").is_err());
+    }
+
+    #[test]
+    fn test_duration_calculator_smart_meridians() {
+        let calculator = DurationCalculator::new().smart_meridians(true);
+        assert_eq!(calculator.calculate("1:00-4:00"), Ok(3.0));
+        assert_eq!(calculator.calculate("12:00-4:00"), Ok(4.0));
+        assert_eq!(calculator.calculate("9:00-5:30"), Ok(8.5));
+        assert_eq!(calculator.calculate("11:00-1:00"), Ok(2.0));
+        assert_eq!(calculator.calculate("9:00-9:00"), Ok(12.0));
+        assert_eq!(calculator.calculate("midnight-5:00"), Ok(5.0));
+        assert_eq!(calculator.calculate("7:00-8:00"), Ok(1.0));
+        assert_eq!(calculator.calculate("1:00AM-4:00PM"), Ok(15.0));
+        assert_eq!(calculator.calculate("13:00-17:00"), Ok(4.0));
+        assert!(calculator.calculate("1:00AM-4:00").is_err());
+
+        assert_eq!(DurationCalculator::new().calculate("1:00-4:00"), Ok(15.0));
+    }
+
+    #[test]
+    fn test_duration_calculator_noon_start() {
+        let calculator = DurationCalculator::new().noon_start(true);
+        assert_eq!(calculator.calculate("12:00-1:00"), Ok(1.0));
+        assert_eq!(calculator.calculate("12:30-5:00"), Ok(4.5));
+        assert_eq!(calculator.calculate("9:00-5:30"), Ok(8.5));
+        assert_eq!(calculator.calculate("12:00AM-1:00PM"), Ok(13.0));
+        assert_eq!(calculator.calculate("12:00PM-1:00PM"), Ok(1.0));
+        assert_eq!(calculator.calculate("midnight-5:00"), Ok(17.0));
+        assert_eq!(calculator.calculate("12:00-13:00"), Ok(1.0));
+        assert_eq!(
+            calculator
+                .clone()
+                .implicit_meridians(ImplicitMeridians {
+                    start: Meridian::Am,
+                    end: Meridian::Am,
+                })
+                .calculate("12:00-1:00"),
+            Ok(1.0)
+        );
+
+        assert_eq!(DurationCalculator::new().calculate("12:00-1:00"), Ok(13.0));
+    }
+}
@@ -0,0 +1,436 @@
+use crate::time_parsing::ClockFormat;
+use crate::TimeSpan;
+use alloc::boxed::Box;
+use alloc::string::String;
+
+/// Represents an error that can occur during time parsing or calculation.
+///
+/// Where a variant has a `position`, it is the byte offset within `input` of the offending
+/// character, e.g. the first non-digit of a minute, for underlining the problem in an editor.
+/// It is `None` when the problem is not at one place, such as a missing range separator.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeError {
+    /// The time or range string does not have the expected overall shape.
+    InvalidFormat {
+        input: String,
+        issue: FormatIssue,
+        position: Option<usize>,
+    },
+    /// The hour part of `input` is malformed or out of range for `clock`.
+    InvalidHour {
+        input: String,
+        hour: String,
+        clock: ClockFormat,
+        issue: FieldIssue,
+        position: Option<usize>,
+    },
+    /// The minute part of `input` is malformed or not within 0-59.
+    InvalidMinute {
+        input: String,
+        minute: String,
+        issue: FieldIssue,
+        position: Option<usize>,
+    },
+    /// The second part of `input` is malformed or not within 0-59.
+    InvalidSecond {
+        input: String,
+        second: String,
+        issue: FieldIssue,
+        position: Option<usize>,
+    },
+    /// An AM/PM indicator other than "AM" or "PM" reached the conversion to minutes.
+    InvalidMeridian { input: String, meridian: String },
+    /// Only one side of the range specifies AM/PM.
+    AmbiguousRange { input: String },
+    /// The end of a single-day range resolves to a time before its start. `start_minutes`
+    /// and `end_minutes` are the interpreted times as whole minutes from midnight, or for a
+    /// decimal-hour range, its hours rounded to whole minutes.
+    EndBeforeStart {
+        start: String,
+        start_interpreted: String,
+        start_minutes: u32,
+        end: String,
+        end_interpreted: String,
+        end_minutes: u32,
+    },
+    /// A break to deduct is longer than the range it is deducted from.
+    BreakExceedsSpan { break_minutes: u32, span: TimeSpan },
+    /// The range starts and ends at the same time, which was configured to be rejected.
+    ZeroDuration { input: String },
+    /// A side of a minute range such as "540-1050" is not within 0-1439.
+    InvalidMinuteOfDay { input: String, minutes: String },
+    /// The length of a workday is not a positive number of hours.
+    InvalidWorkdayHours,
+    /// A side of a decimal-hour range such as "1.5h-3h" is not a non-negative number.
+    InvalidDecimalHours { input: String, hours: String },
+    /// No part of the range `input` falls within `window`.
+    OutsideWindow { input: String, window: String },
+    /// Adding `minutes` to `start` would go past the end of the day.
+    PastMidnight { start: String, minutes: u32 },
+    /// A slot step of zero minutes was requested, which would never advance.
+    InvalidStep { step_minutes: u32 },
+    /// Reading the input failed, e.g. a line of a batch was not valid UTF-8.
+    ReadFailed { message: String },
+    /// The input is longer than the calculator accepts; it was rejected before parsing.
+    InputTooLong { length: usize, max_length: usize },
+    /// The range at `index` of a list failed with `error`.
+    AtIndex { index: usize, error: Box<TimeError> },
+    /// A duration such as "8h30m" is malformed, or has 60 or more minutes alongside hours.
+    InvalidDuration { input: String },
+    /// The UTC offset `offset` in `input` is malformed or beyond ±14:00.
+    InvalidOffset { input: String, offset: String },
+    /// The date `date` in `input` has a month outside 1-12 or a day outside 1-31.
+    InvalidDate { input: String, date: String },
+    /// A single-day calculation produced a span longer than 24 hours, which indicates a bug.
+    SpanExceedsDay { span: TimeSpan },
+}
+
+/// Describes why a time or range string has an invalid shape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatIssue {
+    /// The time is too short, or is just an AM/PM indicator.
+    MeridianOnly,
+    /// A space separates the time from its AM/PM indicator, which the strict parser rejects.
+    SpaceBeforeMeridian,
+    /// More than one space separates the time from its AM/PM indicator.
+    MultipleSpacesBeforeMeridian,
+    /// Something other than a digit directly precedes the AM/PM indicator, as in "9:00XAM".
+    MeridianAfterNonDigit,
+    /// An AM/PM indicator was given where a 24-hour time was expected.
+    MeridianIn24Hour,
+    /// The time is missing colons or has too many.
+    ColonCount,
+    /// The range does not split into exactly a start and an end on `separator`.
+    RangeSeparator { separator: String },
+    /// The start or end of the range is empty after splitting on `separator`.
+    EmptyRangeSide { separator: String },
+    /// An empty string was given as the range separator.
+    EmptySeparator,
+    /// The range at `index` of a comma-separated list is empty.
+    EmptyRange { index: usize },
+    /// The entry at `index` of a labeled timesheet has no "Label:" prefix.
+    MissingLabel { index: usize },
+    /// The input is not a JSON array of strings.
+    JsonArray,
+}
+
+/// Describes why an hour, minute or second field is invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldIssue {
+    /// The field has the wrong number of digits.
+    Length,
+    /// The field is not a number.
+    NotANumber,
+    /// The field is a number outside its allowed range.
+    OutOfRange { value: u32 },
+}
+
+impl core::fmt::Display for TimeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            TimeError::InvalidFormat { input, issue, .. } => match issue {
+                FormatIssue::MeridianOnly => write!(
+                    f,
+                    "Invalid time format: '{}'. Time string is too short or just an AM/PM indicator.",
+                    input
+                ),
+                FormatIssue::SpaceBeforeMeridian => write!(
+                    f,
+                    "Invalid time format: '{}'. Remove the space before AM/PM, or use lenient parsing.",
+                    input
+                ),
+                FormatIssue::MultipleSpacesBeforeMeridian => write!(
+                    f,
+                    "Invalid time format: '{}'. Use at most one space between the time and AM/PM.",
+                    input
+                ),
+                FormatIssue::MeridianAfterNonDigit => write!(
+                    f,
+                    "Invalid time format: '{}'. AM/PM must directly follow the last digit of the time, e.g. '9:00AM'.",
+                    input
+                ),
+                FormatIssue::MeridianIn24Hour => write!(
+                    f,
+                    "Invalid time format: '{}'. AM/PM is not allowed in 24-hour format.",
+                    input
+                ),
+                FormatIssue::ColonCount => write!(
+                    f,
+                    "Invalid time format: '{}'. Expected H:MM, HH:MM or HH:MM:SS (optionally followed by AM/PM). Missing or too many colons.",
+                    input
+                ),
+                FormatIssue::RangeSeparator { separator } => write!(
+                    f,
+                    "Invalid input format: '{}'. Expected format is H(H):MM[:SS][am/pm]{}H(H):MM[:SS][am/pm].",
+                    input, separator
+                ),
+                FormatIssue::EmptyRangeSide { separator } => write!(
+                    f,
+                    "Invalid input format: '{}'. Start or end time string is empty after splitting by '{}'.",
+                    input, separator
+                ),
+                FormatIssue::EmptySeparator => write!(
+                    f,
+                    "Invalid input format: '{}'. The range separator must not be empty.",
+                    input
+                ),
+                FormatIssue::EmptyRange { index } => write!(
+                    f,
+                    "Invalid input format: '{}'. Range at index {} is empty.",
+                    input, index
+                ),
+                FormatIssue::JsonArray => write!(
+                    f,
+                    "Invalid input format: '{}'. Expected a JSON array of ranges, e.g. [\"9:00AM-11:00AM\"].",
+                    input
+                ),
+                FormatIssue::MissingLabel { index } => write!(
+                    f,
+                    "Invalid input format: '{}'. Entry at index {} needs a label, e.g. 'Mon:9:00-5:00'.",
+                    input, index
+                ),
+            },
+            TimeError::InvalidHour {
+                input,
+                hour,
+                clock,
+                issue,
+                ..
+            } => match issue {
+                FieldIssue::Length => write!(
+                    f,
+                    "Invalid hour format in '{}'. Hour part '{}' must be 1 or 2 digits.",
+                    input, hour
+                ),
+                FieldIssue::NotANumber => write!(
+                    f,
+                    "Invalid hour value: '{}' in '{}'. Hour must be a number.",
+                    hour, input
+                ),
+                FieldIssue::OutOfRange { value } => write!(
+                    f,
+                    "Invalid hour: {}. Hour must be between {} and {} for {} format in '{}'.",
+                    value,
+                    clock.hour_range().start(),
+                    clock.hour_range().end(),
+                    clock,
+                    input
+                ),
+            },
+            TimeError::InvalidMinute {
+                input,
+                minute,
+                issue,
+                ..
+            } => match issue {
+                FieldIssue::Length => write!(
+                    f,
+                    "Invalid minute format in '{}'. Minute part '{}' must be 2 digits.",
+                    input, minute
+                ),
+                FieldIssue::NotANumber => write!(
+                    f,
+                    "Invalid minute value: '{}' in '{}'. Minute must be a number.",
+                    minute, input
+                ),
+                FieldIssue::OutOfRange { value } => write!(
+                    f,
+                    "Invalid minute: {}. Minute must be between 0 and 59 in '{}'.",
+                    value, input
+                ),
+            },
+            TimeError::InvalidSecond {
+                input,
+                second,
+                issue,
+                ..
+            } => match issue {
+                FieldIssue::Length => write!(
+                    f,
+                    "Invalid second format in '{}'. Second part '{}' must be 2 digits.",
+                    input, second
+                ),
+                FieldIssue::NotANumber => write!(
+                    f,
+                    "Invalid second value: '{}' in '{}'. Second must be a number.",
+                    second, input
+                ),
+                FieldIssue::OutOfRange { value } => write!(
+                    f,
+                    "Invalid second: {}. Second must be between 0 and 59 in '{}'.",
+                    value, input
+                ),
+            },
+            TimeError::InvalidMeridian { input, meridian } => write!(
+                f,
+                "Internal error or invalid AM/PM indicator: '{}' for time '{}'. Expected 'AM' or 'PM'.",
+                meridian, input
+            ),
+            TimeError::AmbiguousRange { input } => write!(
+                f,
+                "Ambiguous time range: '{}'. Both times must specify AM/PM, or neither should. If neither, start is assumed AM and end is assumed PM.",
+                input
+            ),
+            TimeError::EndBeforeStart {
+                start,
+                start_interpreted,
+                end,
+                end_interpreted,
+                ..
+            } => write!(
+                f,
+                "End time {} (interpreted as {}) is before start time {} (interpreted as {}). The range must be within a single day and end time must be after start time.",
+                end, end_interpreted, start, start_interpreted
+            ),
+            TimeError::BreakExceedsSpan {
+                break_minutes,
+                span,
+            } => write!(
+                f,
+                "Break of {} minutes is longer than the range duration of {}.",
+                break_minutes, span
+            ),
+            TimeError::ZeroDuration { input } => write!(
+                f,
+                "Zero-length time range: '{}'. The start and end times are the same.",
+                input
+            ),
+            TimeError::InvalidMinuteOfDay { input, minutes } => write!(
+                f,
+                "Invalid minutes from midnight: '{}' in '{}'. Must be between 0 and 1439.",
+                minutes, input
+            ),
+            TimeError::InvalidWorkdayHours => {
+                write!(f, "Invalid workday length. Workday hours must be greater than 0.")
+            }
+            TimeError::InvalidDecimalHours { input, hours } => write!(
+                f,
+                "Invalid decimal hours: '{}' in '{}'. Expected a non-negative number such as 1.5 or 1.5h.",
+                hours, input
+            ),
+            TimeError::OutsideWindow { input, window } => write!(
+                f,
+                "Time range '{}' does not overlap the window {}.",
+                input, window
+            ),
+            TimeError::PastMidnight { start, minutes } => write!(
+                f,
+                "Adding {} minutes to {} goes past the end of the day. The result must be 11:59PM or earlier.",
+                minutes, start
+            ),
+            TimeError::InvalidStep { step_minutes } => write!(
+                f,
+                "Invalid slot step: {} minutes. The step must be at least 1 minute.",
+                step_minutes
+            ),
+            TimeError::ReadFailed { message } => write!(f, "Error reading input: {}", message),
+            TimeError::InputTooLong { length, max_length } => write!(
+                f,
+                "Input is {} bytes long, which exceeds the maximum of {} bytes.",
+                length, max_length
+            ),
+            TimeError::InvalidDuration { input } => write!(
+                f,
+                "Invalid duration: '{}'. Expected hours and/or minutes such as 8h, 30m or 8h30m, with under 60 minutes alongside hours.",
+                input
+            ),
+            TimeError::InvalidOffset { input, offset } => write!(
+                f,
+                "Invalid UTC offset '{}' in '{}'. Expected ±HH:MM between -14:00 and +14:00.",
+                offset, input
+            ),
+            TimeError::InvalidDate { input, date } => write!(
+                f,
+                "Invalid date '{}' in '{}'. Expected YYYY-MM-DD with a month of 1-12 and a day of 1-31.",
+                date, input
+            ),
+            TimeError::AtIndex { index, error } => {
+                write!(f, "Range at index {}: {}", index, error)
+            }
+            TimeError::SpanExceedsDay { span } => write!(
+                f,
+                "Internal error: a single-day range produced a span of {}, which exceeds 24 hours.",
+                span
+            ),
+        }
+    }
+}
+
+impl TimeError {
+    /// Returns the byte offset within the error's input where parsing failed, if known.
+    pub fn position(&self) -> Option<usize> {
+        match self {
+            TimeError::InvalidFormat { position, .. }
+            | TimeError::InvalidHour { position, .. }
+            | TimeError::InvalidMinute { position, .. }
+            | TimeError::InvalidSecond { position, .. } => *position,
+            _ => None,
+        }
+    }
+}
+
+/// Returns the byte offset of `part` within `whole`, or `None` if `part` is not a slice of it.
+pub(crate) fn offset_in(part: &str, whole: &str) -> Option<usize> {
+    let offset = (part.as_ptr() as usize).checked_sub(whole.as_ptr() as usize)?;
+    (offset + part.len() <= whole.len()).then_some(offset)
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for TimeError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn test_time_error_display() {
+        assert_eq!(
+            TimeError::InvalidHour {
+                input: "13:00AM".to_string(),
+                hour: "13".to_string(),
+                clock: ClockFormat::TwelveHour,
+                issue: FieldIssue::OutOfRange { value: 13 },
+                position: Some(0),
+            }
+            .to_string(),
+            "Invalid hour: 13. Hour must be between 1 and 12 for 12-hour format in '13:00AM'."
+        );
+        assert_eq!(
+            TimeError::InvalidMinute {
+                input: "09:0AM".to_string(),
+                minute: "0".to_string(),
+                issue: FieldIssue::Length,
+                position: Some(3),
+            }
+            .to_string(),
+            "Invalid minute format in '09:0AM'. Minute part '0' must be 2 digits."
+        );
+        assert_eq!(
+            TimeError::AmbiguousRange {
+                input: "09:00AM-05:00".to_string(),
+            }
+            .to_string(),
+            "Ambiguous time range: '09:00AM-05:00'. Both times must specify AM/PM, or neither should. If neither, start is assumed AM and end is assumed PM."
+        );
+    }
+
+    #[test]
+    fn test_time_error_clone_and_eq() {
+        let error = TimeError::AtIndex {
+            index: 1,
+            error: Box::new(TimeError::InvalidFormat {
+                input: "9:00".into(),
+                issue: FormatIssue::RangeSeparator {
+                    separator: "-".into(),
+                },
+                position: None,
+            }),
+        };
+        let copy = error.clone();
+        assert_eq!(copy, error);
+        assert_eq!(copy.to_string(), error.to_string());
+        let errors = [error.clone(), copy];
+        assert!(errors.iter().all(|e| *e == error));
+    }
+}
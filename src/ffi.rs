@@ -0,0 +1,69 @@
+//! C entry points, enabled by the `ffi` feature. Build a shared library with
+//! `cargo rustc --release --features ffi --lib --crate-type cdylib`.
+
+use crate::time_difference::calculate;
+use std::ffi::{c_char, c_int, CStr};
+use std::panic;
+
+/// `tdc_calculate` succeeded and wrote the hours.
+pub const TDC_OK: c_int = 0;
+/// `range` or `out_hours` was null.
+pub const TDC_ERR_NULL: c_int = -1;
+/// `range` was not valid UTF-8.
+pub const TDC_ERR_UTF8: c_int = -2;
+/// The range could not be calculated, e.g. it was malformed or ended before it started.
+pub const TDC_ERR_CALCULATION: c_int = -3;
+/// The calculation panicked; this indicates a bug in the library.
+pub const TDC_ERR_PANIC: c_int = -4;
+
+/// Calculates the hours in a NUL-terminated range string such as "9:00AM-5:30PM", writing
+/// them to `out_hours`. Returns `TDC_OK` (0) on success or one of the negative `TDC_ERR_*`
+/// codes, in which case `out_hours` is left untouched.
+///
+/// # Safety
+///
+/// `range` must be null or point to a NUL-terminated string, and `out_hours` must be null
+/// or point to memory writable as an `f64`.
+#[no_mangle]
+pub unsafe extern "C" fn tdc_calculate(range: *const c_char, out_hours: *mut f64) -> c_int {
+    if range.is_null() || out_hours.is_null() {
+        return TDC_ERR_NULL;
+    }
+    let Ok(range_str) = CStr::from_ptr(range).to_str() else {
+        return TDC_ERR_UTF8;
+    };
+    match panic::catch_unwind(|| calculate(range_str)) {
+        Ok(Ok(hours)) => {
+            *out_hours = hours;
+            TDC_OK
+        }
+        Ok(Err(_)) => TDC_ERR_CALCULATION,
+        Err(_) => TDC_ERR_PANIC,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+
+    #[test]
+    fn test_tdc_calculate() {
+        let mut hours = 0.0;
+        unsafe {
+            assert_eq!(tdc_calculate(c"9:00AM-5:30PM".as_ptr(), &mut hours), TDC_OK);
+            assert_eq!(hours, 8.5);
+            assert_eq!(
+                tdc_calculate(c"5:00PM-9:00AM".as_ptr(), &mut hours),
+                TDC_ERR_CALCULATION
+            );
+            assert_eq!(hours, 8.5);
+            assert_eq!(tdc_calculate(c"\xff".as_ptr(), &mut hours), TDC_ERR_UTF8);
+            assert_eq!(tdc_calculate(ptr::null(), &mut hours), TDC_ERR_NULL);
+            assert_eq!(
+                tdc_calculate(c"9:00AM-5:30PM".as_ptr(), ptr::null_mut()),
+                TDC_ERR_NULL
+            );
+        }
+    }
+}
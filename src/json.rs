@@ -0,0 +1,72 @@
+//! JSON input and output for lists of ranges, enabled by the `json` feature.
+
+use crate::time_difference::calculate;
+use crate::{FormatIssue, TimeError};
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Calculates each range of a JSON array such as `["9:00AM-11:00AM","1:00PM-5:00PM"]` and
+/// returns the durations in hours as a JSON array, e.g. `[2.0,4.0]`. A range that fails is
+/// reported as `TimeError::AtIndex` with its position in the array.
+pub fn calculate_many_json(json: &str) -> Result<String, TimeError> {
+    let ranges: Vec<String> = serde_json::from_str(json).map_err(|_| TimeError::InvalidFormat {
+        input: json.to_string(),
+        issue: FormatIssue::JsonArray,
+        position: None,
+    })?;
+    let hours = ranges
+        .iter()
+        .enumerate()
+        .map(|(index, range)| {
+            calculate(range).map_err(|error| TimeError::AtIndex {
+                index,
+                error: Box::new(error),
+            })
+        })
+        .collect::<Result<Vec<f64>, TimeError>>()?;
+    // A list of finite floats always serializes.
+    Ok(serde_json::to_string(&hours).expect("durations serialize as JSON"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calculate_many_json() {
+        assert_eq!(
+            calculate_many_json(r#"["9:00AM-11:00AM", "1:00PM-5:00PM"]"#),
+            Ok("[2.0,4.0]".to_string())
+        );
+        assert_eq!(calculate_many_json("[]"), Ok("[]".to_string()));
+        assert_eq!(
+            calculate_many_json(r#"["9:00-5:30"]"#),
+            Ok("[8.5]".to_string())
+        );
+    }
+
+    #[test]
+    fn test_calculate_many_json_errors() {
+        let error = calculate_many_json(r#"["9:00AM-11:00AM", "5:00PM-9:00AM"]"#).unwrap_err();
+        assert!(matches!(
+            &error,
+            TimeError::AtIndex { index: 1, error } if matches!(**error, TimeError::EndBeforeStart { .. })
+        ));
+        assert!(error.to_string().starts_with("Range at index 1: End time"));
+        for input in [
+            "",
+            "[1, 2]",
+            r#"{"a": "9:00AM-5:00PM"}"#,
+            r#"["9:00AM-5:00PM""#,
+        ] {
+            assert!(matches!(
+                calculate_many_json(input),
+                Err(TimeError::InvalidFormat {
+                    issue: FormatIssue::JsonArray,
+                    ..
+                })
+            ));
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Calculates the duration between two times of day, e.g. "9:00AM-5:30PM".
+//!
+//! The calculation itself only needs `core` and `alloc`; the default `std` feature
+//! adds the `std::error::Error` impl, and the default `clock` feature reads the system clock
+//! for `TimeOfDay::now` and `hours_since` and enables the command-line tool. The `ffi` feature adds
+//! C entry points in the `ffi` module. The `serde` feature serializes `TimeOfDay` and
+//! `TimeRange` as their canonical strings, and the `json` feature adds `calculate_many_json`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+mod accumulator;
+#[cfg(feature = "std")]
+mod batch;
+#[cfg(feature = "clock")]
+mod clock;
+mod duration_calculator;
+mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "json")]
+mod json;
+mod messages;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod time_conversion;
+mod time_dates;
+mod time_difference;
+mod time_formatting;
+mod time_of_day;
+mod time_offset;
+mod time_parsing;
+mod time_range;
+mod time_rounding;
+mod time_slots;
+mod time_span;
+mod weekly;
+
+pub use accumulator::DurationAccumulator;
+#[cfg(feature = "std")]
+pub use batch::{process_lines, process_range_lines, process_range_lines_with};
+#[cfg(feature = "clock")]
+pub use clock::{hours_since, hours_until};
+pub use duration_calculator::{DurationCalculator, DEFAULT_MAX_INPUT_LENGTH};
+pub use error::{FieldIssue, FormatIssue, TimeError};
+#[cfg(feature = "json")]
+pub use json::calculate_many_json;
+pub use messages::MessageCatalog;
+pub use time_conversion::to_minutes as convert_components_to_minutes;
+pub use time_conversion::to_seconds as convert_components_to_seconds;
+pub use time_conversion::{hour12_to_hour24, time_to_minutes, ImplicitMeridians, Meridian};
+pub use time_dates::calculate_with_dates;
+pub use time_difference::billable_hours;
+pub use time_difference::calculate as calculate_time_difference_from_range_str;
+pub use time_difference::calculate_24h as calculate_time_difference_24h;
+pub use time_difference::calculate_allowing_overnight as calculate_time_difference_allowing_overnight;
+pub use time_difference::calculate_decimal_hours as calculate_decimal_hour_range;
+pub use time_difference::calculate_from_minutes as calculate_from_minute_range;
+pub use time_difference::calculate_multiday;
+pub use time_difference::calculate_range;
+pub use time_difference::calculate_range_allowing_overnight;
+pub use time_difference::calculate_range_minutes;
+pub use time_difference::calculate_rounded as calculate_time_difference_rounded;
+pub use time_difference::calculate_snapped;
+pub use time_difference::calculate_with_implicit_meridians;
+pub use time_difference::calculate_with_separator as calculate_time_difference_with_separator;
+pub use time_difference::calculate_worked_hours;
+pub use time_difference::count_minutes_inclusive;
+pub use time_difference::fraction_of_workday;
+pub use time_difference::hours_and_minutes;
+pub use time_difference::is_ambiguous;
+pub use time_difference::signed as signed_time_difference;
+pub use time_difference::span as calculate_time_span;
+pub use time_difference::sum as sum_time_ranges;
+pub use time_difference::to_std_duration as range_to_std_duration;
+pub use time_difference::validate as validate_range;
+pub use time_difference::{RangeCalculation, RangeMinutes};
+pub use time_formatting::{format_hours_hhmm, format_iso8601};
+pub use time_of_day::{add_duration, add_duration_allowing_overnight, TimeOfDay};
+pub use time_offset::calculate_with_offsets;
+pub use time_parsing::{
+    parse_time, parse_time_components, parse_time_components_24h, parse_time_lenient, ClockFormat,
+    ParsedTime,
+};
+pub use time_range::{
+    clamp_to_window, clamp_to_window_or_error, midpoint, normalize_range, overlap_minutes,
+    ranges_overlap, TimeRange,
+};
+pub use time_rounding::RoundingMode;
+pub use time_slots::{quarter_hour_slots, slots_with_step};
+pub use time_span::{parse_duration, TimeSpan};
+pub use weekly::{parse_weekly, weekly_total};
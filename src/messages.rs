@@ -0,0 +1,143 @@
+use crate::time_parsing::ClockFormat;
+use crate::{FieldIssue, FormatIssue, TimeError};
+use alloc::string::{String, ToString};
+
+/// Replacement text for `TimeError` messages, e.g. to show errors in another language.
+///
+/// Each method receives the fields of one error variant and returns the message to show,
+/// or `None` to keep the English message from `TimeError`'s `Display`. Variants without a
+/// method here always use the English message.
+pub trait MessageCatalog {
+    /// The message for `TimeError::InvalidFormat`.
+    fn invalid_format(&self, _input: &str, _issue: &FormatIssue) -> Option<String> {
+        None
+    }
+
+    /// The message for `TimeError::InvalidHour`.
+    fn invalid_hour(
+        &self,
+        _input: &str,
+        _hour: &str,
+        _clock: ClockFormat,
+        _issue: &FieldIssue,
+    ) -> Option<String> {
+        None
+    }
+
+    /// The message for `TimeError::InvalidMinute`.
+    fn invalid_minute(&self, _input: &str, _minute: &str, _issue: &FieldIssue) -> Option<String> {
+        None
+    }
+
+    /// The message for `TimeError::AmbiguousRange`.
+    fn ambiguous_range(&self, _input: &str) -> Option<String> {
+        None
+    }
+
+    /// The message for `TimeError::EndBeforeStart`. `start_minutes` and `end_minutes` are the
+    /// interpreted times as minutes from midnight, for formatting them in the local style.
+    fn end_before_start(
+        &self,
+        _start: &str,
+        _start_interpreted: &str,
+        _start_minutes: u32,
+        _end: &str,
+        _end_interpreted: &str,
+        _end_minutes: u32,
+    ) -> Option<String> {
+        None
+    }
+}
+
+impl TimeError {
+    /// Returns this error's message from `catalog`, falling back to the English message.
+    pub fn localized(&self, catalog: &dyn MessageCatalog) -> String {
+        let message = match self {
+            TimeError::InvalidFormat { input, issue, .. } => catalog.invalid_format(input, issue),
+            TimeError::InvalidHour {
+                input,
+                hour,
+                clock,
+                issue,
+                ..
+            } => catalog.invalid_hour(input, hour, *clock, issue),
+            TimeError::InvalidMinute {
+                input,
+                minute,
+                issue,
+                ..
+            } => catalog.invalid_minute(input, minute, issue),
+            TimeError::AmbiguousRange { input } => catalog.ambiguous_range(input),
+            TimeError::EndBeforeStart {
+                start,
+                start_interpreted,
+                start_minutes,
+                end,
+                end_interpreted,
+                end_minutes,
+            } => catalog.end_before_start(
+                start,
+                start_interpreted,
+                *start_minutes,
+                end,
+                end_interpreted,
+                *end_minutes,
+            ),
+            _ => None,
+        };
+        message.unwrap_or_else(|| self.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculate_time_difference_from_range_str as calculate;
+    use alloc::format;
+
+    struct German;
+
+    impl MessageCatalog for German {
+        fn ambiguous_range(&self, input: &str) -> Option<String> {
+            Some(format!("Mehrdeutiger Zeitraum: '{}'.", input))
+        }
+
+        fn end_before_start(
+            &self,
+            start: &str,
+            _start_interpreted: &str,
+            start_minutes: u32,
+            end: &str,
+            _end_interpreted: &str,
+            end_minutes: u32,
+        ) -> Option<String> {
+            Some(format!(
+                "Ende {} ({:02}.{:02} Uhr) liegt vor Beginn {} ({:02}.{:02} Uhr).",
+                end,
+                end_minutes / 60,
+                end_minutes % 60,
+                start,
+                start_minutes / 60,
+                start_minutes % 60
+            ))
+        }
+    }
+
+    #[test]
+    fn test_localized_overrides() {
+        assert_eq!(
+            calculate("09:00AM-05:00").unwrap_err().localized(&German),
+            "Mehrdeutiger Zeitraum: '09:00AM-05:00'."
+        );
+        assert_eq!(
+            calculate("5:00PM-9:00AM").unwrap_err().localized(&German),
+            "Ende 9:00AM (09.00 Uhr) liegt vor Beginn 5:00PM (17.00 Uhr)."
+        );
+    }
+
+    #[test]
+    fn test_localized_falls_back_to_english() {
+        let error = calculate("13:00AM-5:00PM").unwrap_err();
+        assert_eq!(error.localized(&German), error.to_string());
+    }
+}
@@ -0,0 +1,93 @@
+//! `Serialize`/`Deserialize` for the time types, enabled by the `serde` feature. Each type is
+//! written as its canonical string and read back by parsing, so `TimeError`s surface as
+//! deserialization errors.
+
+use crate::{TimeOfDay, TimeRange};
+use core::fmt;
+use core::marker::PhantomData;
+use core::str::FromStr;
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+impl Serialize for TimeOfDay {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl Serialize for TimeRange {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeOfDay {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(ParseVisitor::new("a time such as \"9:00AM\""))
+    }
+}
+
+impl<'de> Deserialize<'de> for TimeRange {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(ParseVisitor::new("a time range such as \"9:00AM-5:30PM\""))
+    }
+}
+
+/// Deserializes any `FromStr` type by parsing a string.
+struct ParseVisitor<T> {
+    expecting: &'static str,
+    marker: PhantomData<T>,
+}
+
+impl<T> ParseVisitor<T> {
+    fn new(expecting: &'static str) -> Self {
+        ParseVisitor {
+            expecting,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Visitor<'_> for ParseVisitor<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str(self.expecting)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<T, E> {
+        value.parse().map_err(E::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serialize_canonical_strings() {
+        let time: TimeOfDay = "9:00am".parse().unwrap();
+        assert_eq!(serde_json::to_string(&time).unwrap(), "\"09:00AM\"");
+        let range: TimeRange = "9:00-5:30".parse().unwrap();
+        assert_eq!(
+            serde_json::to_string(&range).unwrap(),
+            "\"09:00AM-05:30PM\""
+        );
+    }
+
+    #[test]
+    fn test_deserialize_by_parsing() {
+        let range: TimeRange = serde_json::from_str("\"9:00AM-5:30PM\"").unwrap();
+        assert_eq!(range.duration_hours(), 8.5);
+        let time: TimeOfDay = serde_json::from_str("\"5:30PM\"").unwrap();
+        assert_eq!(time.minutes_from_midnight(), 1050);
+
+        let error = serde_json::from_str::<TimeRange>("\"5:00PM-9:00AM\"").unwrap_err();
+        assert!(error.to_string().contains("is before start time"));
+        assert!(serde_json::from_str::<TimeOfDay>("900").is_err());
+    }
+}
@@ -0,0 +1,174 @@
+use crate::time_parsing::parse_time_components;
+use crate::TimeError;
+use alloc::format;
+use alloc::string::ToString;
+
+/// A 12-hour clock meridian.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Meridian {
+    Am,
+    Pm,
+}
+
+impl Meridian {
+    /// Returns the indicator as written in time strings, "AM" or "PM".
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Meridian::Am => "AM",
+            Meridian::Pm => "PM",
+        }
+    }
+}
+
+/// The AM/PM assumed for each end of a range when neither time specifies one.
+/// The default assumes the start is AM and the end is PM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImplicitMeridians {
+    pub start: Meridian,
+    pub end: Meridian,
+}
+
+impl Default for ImplicitMeridians {
+    fn default() -> Self {
+        ImplicitMeridians {
+            start: Meridian::Am,
+            end: Meridian::Pm,
+        }
+    }
+}
+
+/// Converts a 12-hour clock hour (1-12) and "AM"/"PM" into a 24-hour clock hour (0-23):
+/// 12AM is 0, 1AM is 1, 12PM is 12 and 1PM is 13. Any other indicator is an error.
+pub fn hour12_to_hour24(hour12: u32, ampm_indicator: &str) -> Result<u32, TimeError> {
+    convert_hour(
+        hour12,
+        ampm_indicator,
+        &format!("{}{}", hour12, ampm_indicator),
+    )
+}
+
+/// Like `hour12_to_hour24`, reporting errors against `original_time_str`.
+fn convert_hour(
+    hour12: u32,
+    ampm_indicator: &str,
+    original_time_str: &str,
+) -> Result<u32, TimeError> {
+    match ampm_indicator {
+        "AM" if hour12 == 12 => Ok(0),
+        "AM" => Ok(hour12),
+        "PM" if hour12 == 12 => Ok(12),
+        "PM" => Ok(hour12 + 12),
+        _ => Err(TimeError::InvalidMeridian {
+            input: original_time_str.to_string(),
+            meridian: ampm_indicator.to_string(),
+        }),
+    }
+}
+
+/// Converts 12-hour format components (hour, minute, AM/PM) into total minutes from midnight.
+pub fn to_minutes(
+    hour12: u32,
+    minute: u32,
+    ampm_indicator: &str,
+    original_time_str: &str,
+) -> Result<u32, TimeError> {
+    Ok(convert_hour(hour12, ampm_indicator, original_time_str)? * 60 + minute)
+}
+
+/// Converts 12-hour format components (hour, minute, second, AM/PM) into total seconds from midnight.
+pub fn to_seconds(
+    hour12: u32,
+    minute: u32,
+    second: u32,
+    ampm_indicator: &str,
+    original_time_str: &str,
+) -> Result<u32, TimeError> {
+    Ok(to_minutes(hour12, minute, ampm_indicator, original_time_str)? * 60 + second)
+}
+
+/// Parses a single 12-hour time string into minutes from midnight, e.g. "5:30PM" gives 1050.
+/// A time without AM/PM is read as AM, so "5:30" gives 330; seconds are dropped.
+pub fn time_to_minutes(time_str: &str) -> Result<u32, TimeError> {
+    let (hour12, minute, _second, ampm) = parse_time_components(time_str)?;
+    to_minutes(hour12, minute, ampm.as_deref().unwrap_or("AM"), time_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_of_day::TimeOfDay;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_convert_components_to_minutes_valid() {
+        assert_eq!(to_minutes(9, 0, "AM", "9:00AM"), Ok(9 * 60));
+        assert_eq!(to_minutes(12, 0, "AM", "12:00AM"), Ok(0));
+        assert_eq!(to_minutes(5, 30, "PM", "05:30PM"), Ok(17 * 60 + 30));
+        assert_eq!(to_minutes(12, 0, "PM", "12:00PM"), Ok(12 * 60));
+    }
+
+    #[test]
+    fn test_hour12_to_hour24_corners() {
+        assert_eq!(hour12_to_hour24(12, "AM"), Ok(0));
+        assert_eq!(hour12_to_hour24(12, "PM"), Ok(12));
+        assert_eq!(hour12_to_hour24(1, "AM"), Ok(1));
+        assert_eq!(hour12_to_hour24(1, "PM"), Ok(13));
+        assert_eq!(hour12_to_hour24(11, "PM"), Ok(23));
+        assert_eq!(
+            hour12_to_hour24(9, "XM"),
+            Err(TimeError::InvalidMeridian {
+                input: "9XM".to_string(),
+                meridian: "XM".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_convert_components_to_minutes_invalid_meridian() {
+        assert_eq!(
+            to_minutes(9, 0, "XM", "9:00XM"),
+            Err(TimeError::InvalidMeridian {
+                input: "9:00XM".to_string(),
+                meridian: "XM".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_convert_components_to_seconds_valid() {
+        assert_eq!(to_seconds(9, 0, 30, "AM", "9:00:30AM"), Ok(9 * 3600 + 30));
+        assert_eq!(to_seconds(12, 0, 0, "AM", "12:00:00AM"), Ok(0));
+        assert_eq!(to_seconds(11, 59, 59, "PM", "11:59:59PM"), Ok(86399));
+        assert!(to_seconds(9, 0, 0, "XM", "9:00:00XM").is_err());
+    }
+
+    #[test]
+    fn test_time_to_minutes() {
+        assert_eq!(time_to_minutes("5:30PM"), Ok(1050));
+        assert_eq!(time_to_minutes("5:30"), Ok(330));
+        assert_eq!(time_to_minutes("12:00AM"), Ok(0));
+        assert_eq!(time_to_minutes("noon"), Ok(720));
+        assert_eq!(time_to_minutes("11:59:59PM"), Ok(1439));
+        assert!(time_to_minutes("13:00PM").is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn prop_minutes_round_trip(
+            hour12 in 1u32..=12,
+            minute in 0u32..60,
+            ampm in prop_oneof![Just("AM"), Just("PM")],
+        ) {
+            let time_str = format!("{}:{:02}{}", hour12, minute, ampm);
+            let minutes = to_minutes(hour12, minute, ampm, &time_str).unwrap();
+            prop_assert!(minutes < 24 * 60);
+            prop_assert_eq!(time_to_minutes(&time_str), Ok(minutes));
+
+            let time = TimeOfDay::from_hms(minutes / 60, minutes % 60, 0).unwrap();
+            prop_assert_eq!(time.hour12(), hour12);
+            prop_assert_eq!(time.minute(), minute);
+            prop_assert_eq!(time.meridian(), ampm);
+            prop_assert_eq!(time.to_string(), format!("{:02}:{:02}{}", hour12, minute, ampm));
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use crate::duration_calculator::DEFAULT_MAX_INPUT_LENGTH;
+use crate::time_conversion::ImplicitMeridians;
+use crate::time_difference::{check_input_length, resolve_sides, split_range};
+use crate::time_of_day::SECONDS_PER_DAY;
+use crate::TimeError;
+use alloc::string::ToString;
+
+/// Calculates the elapsed hours between two times that may each be prefixed by a
+/// `YYYY-MM-DD` date, e.g. "2024-01-01 23:00-2024-01-02 02:00" gives 3.0. The times are read
+/// as by `calculate_time_difference_from_range_str`, and the end must not be before the start.
+///
+/// A time without a date is taken to be on the same day as the other time, so a range with
+/// no dates behaves like `calculate_time_difference_from_range_str`. Dates are only checked
+/// for a month of 1-12 and a day of 1-31; a day past the end of its month runs on into the
+/// next month.
+pub fn calculate_with_dates(range_str: &str) -> Result<f64, TimeError> {
+    check_input_length(range_str, DEFAULT_MAX_INPUT_LENGTH)?;
+    let (start_day, rest) = split_date(range_str.trim_start(), range_str)?;
+    let (raw_start, end) = split_range(rest, "-")?;
+    let (end_day, raw_end) = split_date(end, range_str)?;
+    let range = resolve_sides(
+        range_str,
+        raw_start,
+        raw_end,
+        ImplicitMeridians::default(),
+        false,
+    )?;
+    let (start_day, end_day) = match (start_day, end_day) {
+        (Some(start), Some(end)) => (start, end),
+        (Some(day), None) | (None, Some(day)) => (day, day),
+        (None, None) => return range.span().map(|span| span.as_hours_f64()),
+    };
+    let seconds_per_day = i64::from(SECONDS_PER_DAY);
+    let start = start_day * seconds_per_day + i64::from(range.start.seconds_from_midnight());
+    let end = end_day * seconds_per_day + i64::from(range.end_seconds());
+    if end < start {
+        return Err(range.end_before_start_error());
+    }
+    Ok((end - start) as f64 / 3600.0)
+}
+
+/// Splits a leading `YYYY-MM-DD` date and whitespace off `side`, returning the date as a day
+/// number and the rest of the side. A side that does not start with that shape has no date.
+fn split_date<'a>(side: &'a str, range_str: &str) -> Result<(Option<i64>, &'a str), TimeError> {
+    let bytes = side.as_bytes();
+    let is_date = bytes.len() > 10
+        && bytes[4] == b'-'
+        && bytes[7] == b'-'
+        && [0, 1, 2, 3, 5, 6, 8, 9]
+            .iter()
+            .all(|&i| bytes[i].is_ascii_digit())
+        && bytes[10].is_ascii_whitespace();
+    if !is_date {
+        return Ok((None, side));
+    }
+    let (date, time) = side.split_at(10);
+    // All three fields are known to be plain digits, so parsing cannot fail.
+    let field = |range: core::ops::Range<usize>| date[range].parse::<i64>().unwrap_or(0);
+    let (year, month, day) = (field(0..4), field(5..7), field(8..10));
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return Err(TimeError::InvalidDate {
+            input: range_str.to_string(),
+            date: date.to_string(),
+        });
+    }
+    Ok((Some(days_from_civil(year, month, day)), time.trim_start()))
+}
+
+/// Returns the number of days from 1970-01-01 to the given proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    // Count years from March so that the leap day falls at the end of the year.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_difference::calculate;
+
+    #[test]
+    fn test_calculate_with_dates() {
+        assert_eq!(
+            calculate_with_dates("2024-01-01 23:00-2024-01-02 02:00"),
+            Ok(3.0)
+        );
+        assert_eq!(
+            calculate_with_dates("2024-01-01 11:00PM - 2024-01-03 2:30AM"),
+            Ok(27.5)
+        );
+        assert_eq!(
+            calculate_with_dates("2023-12-31 22:00-2024-01-01 01:00"),
+            Ok(3.0)
+        );
+        assert_eq!(
+            calculate_with_dates("2024-02-28 23:00-2024-03-01 01:00"),
+            Ok(26.0)
+        );
+        assert_eq!(
+            calculate_with_dates("2023-02-28 23:00-2023-03-01 01:00"),
+            Ok(2.0)
+        );
+        assert_eq!(calculate_with_dates("2024-01-01 9:00AM-5:30PM"), Ok(8.5));
+        assert_eq!(calculate_with_dates("9:00AM-2024-01-01 5:30PM"), Ok(8.5));
+    }
+
+    #[test]
+    fn test_calculate_with_dates_matches_calculate_without_dates() {
+        for range in [
+            "9:00AM-5:30PM",
+            "9:00-5:30",
+            "13:00-17:30",
+            "5:00PM-9:00AM",
+            "bad",
+        ] {
+            assert_eq!(calculate_with_dates(range), calculate(range), "{}", range);
+        }
+    }
+
+    #[test]
+    fn test_calculate_with_dates_errors() {
+        assert_eq!(
+            calculate_with_dates("2024-13-01 23:00-2024-01-02 02:00"),
+            Err(TimeError::InvalidDate {
+                input: "2024-13-01 23:00-2024-01-02 02:00".to_string(),
+                date: "2024-13-01".to_string(),
+            })
+        );
+        assert!(calculate_with_dates("2024-01-00 23:00-2024-01-02 02:00").is_err());
+        assert!(calculate_with_dates("2024-01-01 23:00-2024-01-32 02:00").is_err());
+        assert!(matches!(
+            calculate_with_dates("2024-01-02 23:00-2024-01-01 23:30"),
+            Err(TimeError::EndBeforeStart { .. })
+        ));
+        assert!(calculate_with_dates("2024-01-01 23:00-02:00").is_err());
+        assert!(calculate_with_dates("2024-01-01 23:00").is_err());
+    }
+}
@@ -0,0 +1,1547 @@
+use crate::duration_calculator::{DurationCalculator, DEFAULT_MAX_INPUT_LENGTH};
+use crate::error::offset_in;
+use crate::time_conversion::{ImplicitMeridians, Meridian};
+use crate::time_of_day::{seconds_suffix, TimeOfDay, SECONDS_PER_DAY};
+use crate::time_parsing::{
+    is_end_of_day, parse_lenient_24h, parse_lenient_fields, parse_time, parse_time_components_24h,
+    time_keyword, ClockFormat,
+};
+use crate::time_rounding::RoundingMode;
+use crate::time_span::{parse_duration, TimeSpan};
+use crate::{FormatIssue, TimeError};
+use alloc::format;
+use alloc::string::{String, ToString};
+
+/// A range whose start and end have been parsed and resolved to times of day.
+/// `clock` records how the times were read, so errors can show their interpretation,
+/// and `assumed_meridian` whether an implicit AM/PM was applied to either time.
+/// `added_minutes` is set for a range given as a start plus a duration, such as "9:00AM+8h".
+/// `end_of_day` is set for a 24-hour range ending at "24:00", whose `end` is then midnight, so
+/// the end is read through `end_seconds` or `end_time`.
+pub(crate) struct ResolvedRange<'a> {
+    raw_start: &'a str,
+    raw_end: &'a str,
+    pub(crate) start: TimeOfDay,
+    end: TimeOfDay,
+    clock: ClockFormat,
+    pub(crate) assumed_meridian: bool,
+    added_minutes: Option<u32>,
+    end_of_day: bool,
+}
+
+impl ResolvedRange<'_> {
+    fn interpreted(&self, time: TimeOfDay) -> String {
+        match self.clock {
+            ClockFormat::TwelveHour => format!(
+                "{}:{:02}{}{}",
+                time.hour12(),
+                time.minute(),
+                seconds_suffix(time.second()),
+                time.meridian()
+            ),
+            ClockFormat::TwentyFourHour => time.format_24h(),
+        }
+    }
+
+    /// Returns the end as seconds from midnight, which is a whole day for "24:00".
+    pub(crate) fn end_seconds(&self) -> u32 {
+        if self.end_of_day {
+            SECONDS_PER_DAY
+        } else {
+            self.end.seconds_from_midnight()
+        }
+    }
+
+    /// Returns the end as a time of day, which is midnight for "24:00".
+    pub(crate) fn end_time(&self) -> TimeOfDay {
+        self.end
+    }
+
+    /// Returns whether the range ends at "24:00".
+    pub(crate) fn ends_at_end_of_day(&self) -> bool {
+        self.end_of_day
+    }
+
+    pub(crate) fn end_before_start_error(&self) -> TimeError {
+        // A start plus a duration only ends before it starts by wrapping past midnight.
+        if let Some(minutes) = self.added_minutes {
+            return TimeError::PastMidnight {
+                start: self.raw_start.to_string(),
+                minutes,
+            };
+        }
+        TimeError::EndBeforeStart {
+            start: self.raw_start.to_string(),
+            start_interpreted: self.interpreted(self.start),
+            start_minutes: self.start.minutes_from_midnight(),
+            end: self.raw_end.to_string(),
+            end_interpreted: if self.end_of_day {
+                "24:00".to_string()
+            } else {
+                self.interpreted(self.end)
+            },
+            end_minutes: self.end_seconds() / 60,
+        }
+    }
+
+    /// Rejects a start plus a duration whose end wrapped past midnight, which only the
+    /// overnight calculations accept, before the times are compared directly.
+    fn check_not_wrapped(&self) -> Result<(), TimeError> {
+        if self.added_minutes.is_some() && self.end < self.start {
+            return Err(self.end_before_start_error());
+        }
+        Ok(())
+    }
+
+    pub(crate) fn span(&self) -> Result<TimeSpan, TimeError> {
+        let (start, end) = (self.start.seconds_from_midnight(), self.end_seconds());
+        if end < start {
+            return Err(self.end_before_start_error());
+        }
+        check_single_day(TimeSpan::from_seconds(end - start))
+    }
+
+    /// Like `span`, but for a day that starts at `day_start` rather than midnight, so that with
+    /// a 4:00AM day start, 11:00PM-2:00AM is 3 hours while 2:00AM-5:00AM crosses into the next
+    /// day and ends before it starts.
+    pub(crate) fn span_with_day_start(&self, day_start: TimeOfDay) -> Result<TimeSpan, TimeError> {
+        let (start, end) = self.seconds_from_day_start(day_start);
+        if end < start {
+            return Err(self.end_before_start_error());
+        }
+        check_single_day(TimeSpan::from_seconds(end - start))
+    }
+
+    /// Like `span`, but treats an end earlier than the start as falling on the next day.
+    pub(crate) fn overnight_span(&self) -> TimeSpan {
+        self.overnight_span_with_day_start(TimeOfDay::MIDNIGHT)
+    }
+
+    /// Like `overnight_span`, for a day that starts at `day_start`. An end before the start
+    /// falls on the next business day, so the span is the same as for a midnight day start.
+    pub(crate) fn overnight_span_with_day_start(&self, day_start: TimeOfDay) -> TimeSpan {
+        let (start, mut end) = self.seconds_from_day_start(day_start);
+        if end < start {
+            end += SECONDS_PER_DAY;
+        }
+        TimeSpan::from_seconds(end - start)
+    }
+
+    /// Returns the start and end as seconds after `day_start`.
+    fn seconds_from_day_start(&self, day_start: TimeOfDay) -> (u32, u32) {
+        let rebase = |time: TimeOfDay| {
+            (time.seconds_from_midnight() + SECONDS_PER_DAY - day_start.seconds_from_midnight())
+                % SECONDS_PER_DAY
+        };
+        // The end of the day is the last moment before the next day start.
+        let end = if self.end_of_day {
+            SECONDS_PER_DAY - day_start.seconds_from_midnight()
+        } else {
+            rebase(self.end)
+        };
+        (rebase(self.start), end)
+    }
+}
+
+/// Guards against a single-day span longer than 24 hours, which no valid range produces,
+/// so one would indicate a bug rather than bad input.
+fn check_single_day(span: TimeSpan) -> Result<TimeSpan, TimeError> {
+    if span.total_seconds() > SECONDS_PER_DAY {
+        return Err(TimeError::SpanExceedsDay { span });
+    }
+    Ok(span)
+}
+
+/// Deducts an unpaid break from `span`, failing if the break is longer than the span.
+pub(crate) fn deduct_break(span: TimeSpan, break_minutes: u32) -> Result<TimeSpan, TimeError> {
+    span.checked_sub(TimeSpan::from_seconds(break_minutes.saturating_mul(60)))
+        .ok_or(TimeError::BreakExceedsSpan {
+            break_minutes,
+            span,
+        })
+}
+
+/// Dashes that copy-pasted ranges often use in place of '-': the minus sign (U+2212), the
+/// en dash (U+2013) and the em dash (U+2014).
+const DASH_VARIANTS: [char; 3] = ['\u{2212}', '\u{2013}', '\u{2014}'];
+
+/// Rejects an input longer than `max_length` bytes with `TimeError::InputTooLong`, before any
+/// splitting, which bounds the work done on untrusted input.
+pub(crate) fn check_input_length(input: &str, max_length: usize) -> Result<(), TimeError> {
+    if input.len() > max_length {
+        return Err(TimeError::InputTooLong {
+            length: input.len(),
+            max_length,
+        });
+    }
+    Ok(())
+}
+
+/// Splits a range string on the first `separator` into its trimmed, non-empty start and end
+/// parts. Any later separators stay in the end part, where parsing the end time reports them.
+/// With the "-" separator, a range without an ASCII '-' may use one of `DASH_VARIANTS`.
+/// A range longer than `DEFAULT_MAX_INPUT_LENGTH` bytes is rejected first.
+pub(crate) fn split_range<'a>(
+    range_str: &'a str,
+    separator: &str,
+) -> Result<(&'a str, &'a str), TimeError> {
+    check_input_length(range_str, DEFAULT_MAX_INPUT_LENGTH)?;
+    split_sides(range_str, separator)
+}
+
+/// Like `split_range`, without the length check, for a caller that has made its own.
+fn split_sides<'a>(range_str: &'a str, separator: &str) -> Result<(&'a str, &'a str), TimeError> {
+    if separator.is_empty() {
+        return Err(TimeError::InvalidFormat {
+            input: range_str.to_string(),
+            issue: FormatIssue::EmptySeparator,
+            position: None,
+        });
+    }
+    let split = range_str.split_once(separator).or_else(|| match separator {
+        "-" => range_str.split_once(DASH_VARIANTS),
+        _ => None,
+    });
+    let Some((start, end)) = split else {
+        return Err(TimeError::InvalidFormat {
+            input: range_str.to_string(),
+            issue: FormatIssue::RangeSeparator {
+                separator: separator.to_string(),
+            },
+            position: None,
+        });
+    };
+    let raw_start = start.trim();
+    let raw_end = end.trim();
+    if raw_start.is_empty() || raw_end.is_empty() {
+        return Err(TimeError::InvalidFormat {
+            input: range_str.to_string(),
+            issue: FormatIssue::EmptyRangeSide {
+                separator: separator.to_string(),
+            },
+            position: None,
+        });
+    }
+    Ok((raw_start, raw_end))
+}
+
+/// Splits a range string and resolves both times to times of day, without checking
+/// their order. `implicit` supplies the AM/PM when neither time specifies one.
+///
+/// A range where neither time has AM/PM and either hour is past 12, or the end is "24:00",
+/// such as "13:00-17:30", "07:00-19:30" or "22:00-24:00", is read as 24-hour instead, since
+/// no 12-hour reading exists. Hours of 0-12 keep the implicit AM/PM, leading zeros or not,
+/// so "07:00-09:30" is 7:00AM-9:30PM and hour 0 is rejected as in 12-hour times.
+///
+/// With the "-" separator, a range may instead give a start and a duration, such as
+/// "9:00AM+8h", whose end is the start plus the duration as read by `parse_duration`.
+pub(crate) fn resolve<'a>(
+    range_str: &'a str,
+    separator: &str,
+    implicit: ImplicitMeridians,
+) -> Result<ResolvedRange<'a>, TimeError> {
+    check_input_length(range_str, DEFAULT_MAX_INPUT_LENGTH)?;
+    resolve_with(range_str, separator, implicit, false)
+}
+
+/// Like `resolve`, reading times as by `parse_time_lenient` with `lenient`. The length of
+/// `range_str` is not checked, so the caller must have bounded it.
+pub(crate) fn resolve_with<'a>(
+    range_str: &'a str,
+    separator: &str,
+    implicit: ImplicitMeridians,
+    lenient: bool,
+) -> Result<ResolvedRange<'a>, TimeError> {
+    if separator == "-"
+        && !range_str.contains('-')
+        && !range_str.contains(DASH_VARIANTS)
+        && range_str.contains('+')
+    {
+        return resolve_relative(range_str);
+    }
+    let (raw_start, raw_end) = split_sides(range_str, separator)?;
+    resolve_sides(range_str, raw_start, raw_end, implicit, lenient)
+}
+
+/// Like `resolve`, for a range already split into `raw_start` and `raw_end`. With `lenient`,
+/// times are read as by `parse_time_lenient`, and 24-hour times may also use a dot.
+pub(crate) fn resolve_sides<'a>(
+    range_str: &str,
+    raw_start: &'a str,
+    raw_end: &'a str,
+    implicit: ImplicitMeridians,
+    lenient: bool,
+) -> Result<ResolvedRange<'a>, TimeError> {
+    if let Ok(range) = resolve_24h_sides(raw_start, raw_end, lenient) {
+        let is_24h_only = |time: TimeOfDay| time.hour() > 12;
+        if range.end_of_day || is_24h_only(range.start) || is_24h_only(range.end) {
+            return Ok(range);
+        }
+    }
+    let parse = |time_str: &str| {
+        if lenient {
+            parse_lenient_fields(time_str)
+        } else {
+            parse_time(time_str)
+        }
+    };
+    let start = parse(raw_start)?;
+    let end = parse(raw_end)?;
+    let assumed_meridian = start.meridian.is_none() || end.meridian.is_none();
+    let (start_meridian, end_meridian) = match (start.meridian, end.meridian) {
+        (Some(s), Some(e)) => (s, e),
+        (None, None) => (implicit.start, implicit.end),
+        // "noon" and "midnight" fix their own meridian without making the other side ambiguous.
+        (Some(s), None) if time_keyword(raw_start).is_some() => (s, implicit.end),
+        (None, Some(e)) if time_keyword(raw_end).is_some() => (implicit.start, e),
+        _ => {
+            return Err(TimeError::AmbiguousRange {
+                input: range_str.to_string(),
+            })
+        }
+    };
+    Ok(ResolvedRange {
+        raw_start,
+        raw_end,
+        start: TimeOfDay::from_parsed(start, start_meridian, raw_start)?,
+        end: TimeOfDay::from_parsed(end, end_meridian, raw_end)?,
+        clock: ClockFormat::TwelveHour,
+        assumed_meridian,
+        added_minutes: None,
+        end_of_day: false,
+    })
+}
+
+/// Resolves a start plus a duration, such as "9:00AM+8h". The start is read like
+/// `TimeOfDay::parse`, so one without AM/PM is AM. An end past midnight wraps to the next
+/// day, which only the overnight calculations accept; a duration of a day or more is an error.
+fn resolve_relative(range_str: &str) -> Result<ResolvedRange<'_>, TimeError> {
+    let (raw_start, raw_duration) = split_sides(range_str, "+")?;
+    let parsed = parse_time(raw_start)?;
+    let start = TimeOfDay::from_parsed(parsed, parsed.meridian.unwrap_or(Meridian::Am), raw_start)?;
+    let minutes = parse_duration(raw_duration)?;
+    if minutes >= SECONDS_PER_DAY / 60 {
+        return Err(TimeError::PastMidnight {
+            start: raw_start.to_string(),
+            minutes,
+        });
+    }
+    Ok(ResolvedRange {
+        raw_start,
+        raw_end: raw_duration,
+        start,
+        end: start.wrapping_add(TimeSpan::from_minutes(minutes)),
+        clock: ClockFormat::TwelveHour,
+        assumed_meridian: parsed.meridian.is_none(),
+        added_minutes: Some(minutes),
+        end_of_day: false,
+    })
+}
+
+/// Like `resolve`, but reads both times in 24-hour format.
+fn resolve_24h(range_str: &str) -> Result<ResolvedRange<'_>, TimeError> {
+    let (raw_start, raw_end) = split_range(range_str, "-")?;
+    resolve_24h_sides(raw_start, raw_end, false)
+}
+
+/// Resolves the already split sides of a range as 24-hour times, allowing a dot in place of
+/// the colon with `lenient`. An end of "24:00" or "24:00:00" is the end of the day.
+fn resolve_24h_sides<'a>(
+    raw_start: &'a str,
+    raw_end: &'a str,
+    lenient: bool,
+) -> Result<ResolvedRange<'a>, TimeError> {
+    let parse = |time_str: &str| {
+        if lenient {
+            parse_lenient_24h(time_str)
+        } else {
+            parse_time_components_24h(time_str)
+        }
+    };
+    // The 24-hour parser has already validated each component's range.
+    let time_of_day =
+        |(h, m, s): (u32, u32, u32)| TimeOfDay::from_hms(h, m, s).expect("validated 24-hour time");
+    let start = time_of_day(parse(raw_start)?);
+    let end_of_day = is_end_of_day(raw_end);
+    let end = if end_of_day {
+        TimeOfDay::MIDNIGHT
+    } else {
+        time_of_day(parse(raw_end)?)
+    };
+    Ok(ResolvedRange {
+        raw_start,
+        raw_end,
+        start,
+        end,
+        clock: ClockFormat::TwentyFourHour,
+        assumed_meridian: false,
+        added_minutes: None,
+        end_of_day,
+    })
+}
+
+/// Reads a side of a minute range as a time of day, e.g. "1050" as 17:30.
+fn minute_of_day(raw: &str, range_str: &str) -> Result<TimeOfDay, TimeError> {
+    raw.parse::<u32>()
+        .ok()
+        .filter(|minutes| *minutes < 24 * 60)
+        .and_then(|minutes| TimeOfDay::from_hms(minutes / 60, minutes % 60, 0))
+        .ok_or_else(|| TimeError::InvalidMinuteOfDay {
+            input: range_str.to_string(),
+            minutes: raw.to_string(),
+        })
+}
+
+/// Calculates the difference in hours between two time strings.
+/// This is `DurationCalculator::new().calculate(range_str)`.
+pub fn calculate(range_str: &str) -> Result<f64, TimeError> {
+    DurationCalculator::new().calculate(range_str)
+}
+
+/// Checks that `range_str` is a valid range whose end is not before its start, running the
+/// same parsing and checks as `calculate` without computing the duration.
+pub fn validate(range_str: &str) -> Result<(), TimeError> {
+    DurationCalculator::new().validate(range_str)
+}
+
+/// Returns whether `range_str` gives AM/PM on exactly one side, like "9:00AM-5:30", which
+/// calculating reports as `TimeError::AmbiguousRange`, so a caller can ask about it first.
+/// A time that does not parse is still an error; the order of the times is not checked.
+pub fn is_ambiguous(range_str: &str) -> Result<bool, TimeError> {
+    match resolve(range_str, "-", ImplicitMeridians::default()) {
+        Ok(_) => Ok(false),
+        Err(TimeError::AmbiguousRange { .. }) => Ok(true),
+        Err(e) => Err(e),
+    }
+}
+
+/// Calculates the difference between two time strings as a `TimeSpan`.
+pub fn span(range_str: &str) -> Result<TimeSpan, TimeError> {
+    resolve(range_str, "-", ImplicitMeridians::default())?.span()
+}
+
+/// Calculates the difference between two time strings as a `std::time::Duration`,
+/// e.g. 30600 seconds for "9:00AM-5:30PM".
+pub fn to_std_duration(range_str: &str) -> Result<core::time::Duration, TimeError> {
+    span(range_str).map(core::time::Duration::from)
+}
+
+/// Calculates the whole hours and remaining minutes between two time strings, e.g.
+/// (8, 30) for "9:00AM-5:30PM". Computed from the integer span, so there is no float rounding.
+pub fn hours_and_minutes(range_str: &str) -> Result<(u32, u32), TimeError> {
+    span(range_str).map(|span| (span.hours(), span.minutes()))
+}
+
+/// The resolved start and end of a range together with the span between them,
+/// e.g. 9:00AM and 5:30PM for "9:00-5:30", which the AM/PM assumption resolved.
+/// `assumed_meridian` is true when either time's AM/PM came from that assumption.
+/// `end_of_day` is true for a 24-hour range ending at "24:00", such as "22:00-24:00", whose
+/// `end` is then midnight; `end_seconds` accounts for it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeCalculation {
+    pub start: TimeOfDay,
+    pub end: TimeOfDay,
+    pub span: TimeSpan,
+    pub assumed_meridian: bool,
+    pub end_of_day: bool,
+}
+
+impl RangeCalculation {
+    /// Returns the end as seconds from midnight, which is a whole day for "24:00".
+    pub fn end_seconds(&self) -> u32 {
+        if self.end_of_day {
+            SECONDS_PER_DAY
+        } else {
+            self.end.seconds_from_midnight()
+        }
+    }
+}
+
+/// Calculates a range, returning how its start and end were interpreted along with the span.
+pub fn calculate_range(range_str: &str) -> Result<RangeCalculation, TimeError> {
+    let range = resolve(range_str, "-", ImplicitMeridians::default())?;
+    Ok(RangeCalculation {
+        start: range.start,
+        end: range.end,
+        span: range.span()?,
+        assumed_meridian: range.assumed_meridian,
+        end_of_day: range.end_of_day,
+    })
+}
+
+/// Like `calculate_range`, but treats an end time earlier than the start time as falling on
+/// the next day, so "10:00PM-2:00AM" spans 4 hours.
+pub fn calculate_range_allowing_overnight(range_str: &str) -> Result<RangeCalculation, TimeError> {
+    let range = resolve(range_str, "-", ImplicitMeridians::default())?;
+    Ok(RangeCalculation {
+        start: range.start,
+        end: range.end,
+        span: range.overnight_span(),
+        assumed_meridian: range.assumed_meridian,
+        end_of_day: range.end_of_day,
+    })
+}
+
+/// Counts the minutes of a range with both ends included, `end - start + 1`, e.g. for
+/// counting one-minute slots: "9:00AM-9:00AM" is 1 and "9:00AM-9:59AM" is 60. Any seconds are
+/// ignored, so both times count from the start of their minute. Durations elsewhere exclude
+/// the end, where "9:00AM-9:00AM" is 0. An end of "24:00" includes 11:59PM, the last minute of
+/// the day, so "22:00-24:00" is 120.
+pub fn count_minutes_inclusive(range_str: &str) -> Result<u32, TimeError> {
+    let range = calculate_range(range_str)?;
+    let end = if range.end_of_day {
+        24 * 60 - 1
+    } else {
+        range.end.minutes_from_midnight()
+    };
+    Ok(end - range.start.minutes_from_midnight() + 1)
+}
+
+/// Calculates the difference in hours between two time strings, rounding the span
+/// with `mode`, e.g. "9:00AM-9:10AM" with `RoundingMode::CeilQuarter` gives 0.25.
+/// `RoundingMode::Exact` gives the same result as `calculate`.
+pub fn calculate_rounded(range_str: &str, mode: RoundingMode) -> Result<f64, TimeError> {
+    span(range_str).map(|span| mode.apply(span).as_hours_f64())
+}
+
+/// Calculates the billable hours between two time strings, rounded to the nearest tenth of an
+/// hour as by `RoundingMode::NearestTenth`, e.g. 0.1 for the 7 minutes of "9:00AM-9:07AM".
+pub fn billable_hours(range_str: &str) -> Result<f64, TimeError> {
+    calculate_rounded(range_str, RoundingMode::NearestTenth)
+}
+
+/// Calculates the difference in hours after snapping each time to the nearest multiple of
+/// `grid_minutes` from midnight, e.g. "9:07AM-4:53PM" on a 15-minute grid is 9:00AM-5:00PM,
+/// 8.0 hours. A time halfway between two grid points snaps to the later one, seconds are
+/// ignored, and 11:53PM on a 15-minute grid snaps to the end of the day.
+///
+/// The end must not be before the start as written. Snapping keeps that order, though both
+/// times may snap to the same point, giving 0.0. A grid of zero minutes is an error.
+pub fn calculate_snapped(range_str: &str, grid_minutes: u32) -> Result<f64, TimeError> {
+    if grid_minutes == 0 {
+        return Err(TimeError::InvalidStep { step_minutes: 0 });
+    }
+    let range = calculate_range(range_str)?;
+    let snap = |minutes: u32| (minutes + grid_minutes / 2) / grid_minutes * grid_minutes;
+    let (start, end) = (
+        snap(range.start.minutes_from_midnight()),
+        snap(range.end_seconds() / 60),
+    );
+    Ok(f64::from(end - start) / 60.0)
+}
+
+/// A resolved range in whole minutes: the start and end as minutes from midnight and the
+/// duration between them. Displays as a normalized record such as "09:00-17:30 (510m)".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RangeMinutes {
+    pub start: u32,
+    pub end: u32,
+    pub duration_minutes: u32,
+}
+
+impl From<RangeCalculation> for RangeMinutes {
+    fn from(range: RangeCalculation) -> Self {
+        RangeMinutes {
+            start: range.start.minutes_from_midnight(),
+            end: range.end_seconds() / 60,
+            duration_minutes: range.span.total_minutes(),
+        }
+    }
+}
+
+impl core::fmt::Display for RangeMinutes {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}-{:02}:{:02} ({}m)",
+            self.start / 60,
+            self.start % 60,
+            self.end / 60,
+            self.end % 60,
+            self.duration_minutes
+        )
+    }
+}
+
+/// Calculates a range, returning its start, end and duration in minutes from a single parse.
+pub fn calculate_range_minutes(range_str: &str) -> Result<RangeMinutes, TimeError> {
+    calculate_range(range_str).map(RangeMinutes::from)
+}
+
+/// Calculates what fraction of a workday of `workday_hours` a range covers, e.g.
+/// "9:00AM-5:30PM" against an 8-hour day gives 1.0625. `workday_hours` must be greater than 0.
+pub fn fraction_of_workday(range_str: &str, workday_hours: f64) -> Result<f64, TimeError> {
+    // Written so that NaN is rejected too.
+    if !(workday_hours > 0.0 && workday_hours.is_finite()) {
+        return Err(TimeError::InvalidWorkdayHours);
+    }
+    span(range_str).map(|span| span.as_hours_f64() / workday_hours)
+}
+
+/// Calculates the hours worked in a range after deducting an unpaid break,
+/// e.g. "9:00AM-5:30PM" with a 30-minute break gives 8.0.
+pub fn calculate_worked_hours(range_str: &str, break_minutes: u32) -> Result<f64, TimeError> {
+    deduct_break(span(range_str)?, break_minutes).map(|span| span.as_hours_f64())
+}
+
+/// Calculates the difference in hours between two time strings, using `implicit` for the
+/// AM/PM of each end when neither time specifies one.
+///
+/// The assumption is applied before the end-before-start check, so it decides whether a
+/// range is valid: with both ends assumed PM, "1:00-4:00" is 3.0 hours, but "9:00-5:30"
+/// resolves to 9:00PM-5:30PM and is rejected as ending before it starts.
+pub fn calculate_with_implicit_meridians(
+    range_str: &str,
+    implicit: ImplicitMeridians,
+) -> Result<f64, TimeError> {
+    resolve(range_str, "-", implicit)?
+        .span()
+        .map(|span| span.as_hours_f64())
+}
+
+/// Calculates the difference in hours between two time strings separated by `separator`,
+/// e.g. "9:00AM to 5:00PM" with the separator "to". Whitespace around the separator is trimmed.
+///
+/// Negative values can never appear in a time, so '-' is safe as the default separator; inputs
+/// that need to carry signed values should pick a different separator.
+pub fn calculate_with_separator(range_str: &str, separator: &str) -> Result<f64, TimeError> {
+    resolve(range_str, separator, ImplicitMeridians::default())?
+        .span()
+        .map(|span| span.as_hours_f64())
+}
+
+/// Calculates the difference in hours between two times given as minutes from midnight,
+/// e.g. "540-1050" gives 8.5. Each side must be within 0-1439. A range whose sides are not
+/// both plain digits is calculated as a time range like "9:00AM-5:30PM".
+pub fn calculate_from_minutes(range_str: &str) -> Result<f64, TimeError> {
+    let (raw_start, raw_end) = split_range(range_str, "-")?;
+    let is_digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if !is_digits(raw_start) || !is_digits(raw_end) {
+        return calculate(range_str);
+    }
+    let range = ResolvedRange {
+        raw_start,
+        raw_end,
+        start: minute_of_day(raw_start, range_str)?,
+        end: minute_of_day(raw_end, range_str)?,
+        clock: ClockFormat::TwentyFourHour,
+        assumed_meridian: false,
+        added_minutes: None,
+        end_of_day: false,
+    };
+    range.span().map(|span| span.as_hours_f64())
+}
+
+/// Reads a side of a decimal-hour range, e.g. "1.5" or "1.5h", as a number of hours.
+fn decimal_hours(raw: &str, range_str: &str) -> Result<f64, TimeError> {
+    let number = raw
+        .strip_suffix('h')
+        .or_else(|| raw.strip_suffix('H'))
+        .unwrap_or(raw);
+    // Only digits and a decimal point, so signs, exponents, "inf" and "NaN" are rejected.
+    let is_decimal = !number.is_empty() && number.bytes().all(|b| b.is_ascii_digit() || b == b'.');
+    is_decimal
+        .then(|| number.parse::<f64>().ok())
+        .flatten()
+        .ok_or_else(|| TimeError::InvalidDecimalHours {
+            input: range_str.to_string(),
+            hours: raw.to_string(),
+        })
+}
+
+/// Calculates the difference between two decimal-hour values, e.g. "1.5-3" or "1.5h-3h"
+/// gives 1.5. Each side is a non-negative number of hours with an optional 'h' suffix,
+/// and the end must not be less than the start.
+pub fn calculate_decimal_hours(range_str: &str) -> Result<f64, TimeError> {
+    let (raw_start, raw_end) = split_range(range_str, "-")?;
+    let start = decimal_hours(raw_start, range_str)?;
+    let end = decimal_hours(raw_end, range_str)?;
+    if end < start {
+        return Err(TimeError::EndBeforeStart {
+            start: raw_start.to_string(),
+            start_interpreted: format!("{} hours", start),
+            start_minutes: (start * 60.0 + 0.5) as u32,
+            end: raw_end.to_string(),
+            end_interpreted: format!("{} hours", end),
+            end_minutes: (end * 60.0 + 0.5) as u32,
+        });
+    }
+    Ok(end - start)
+}
+
+/// Calculates the difference in hours between two 24-hour time strings, e.g. "13:00-17:30".
+///
+/// An end of "24:00" (or "24:00:00") means the end of the day, so "00:00-24:00" is 24.0.
+/// It is only accepted as the end; "24:00" as a start, or "24:30", is an invalid hour.
+pub fn calculate_24h(range_str: &str) -> Result<f64, TimeError> {
+    resolve_24h(range_str)?
+        .span()
+        .map(|span| span.as_hours_f64())
+}
+
+/// Calculates the difference in hours between two time strings, treating an end
+/// time earlier than the start time as falling on the next day.
+pub fn calculate_allowing_overnight(range_str: &str) -> Result<f64, TimeError> {
+    resolve(range_str, "-", ImplicitMeridians::default())
+        .map(|range| range.overnight_span().as_hours_f64())
+}
+
+/// Calculates the difference in hours between a start time and an end time `day_offset`
+/// days later, e.g. "9:00AM-5:00AM" with an offset of 1 gives 20.0. An offset of 0 behaves
+/// like `calculate`, so the end must not be before the start; the offset itself is unsigned,
+/// so an end can never be placed on an earlier day. A start plus a duration that runs past
+/// midnight, such as "10:00PM+4h", is `TimeError::PastMidnight` whatever the offset.
+pub fn calculate_multiday(range_str: &str, day_offset: u32) -> Result<f64, TimeError> {
+    let range = resolve(range_str, "-", ImplicitMeridians::default())?;
+    range.check_not_wrapped()?;
+    let start_seconds = u64::from(range.start.seconds_from_midnight());
+    let end_seconds =
+        u64::from(range.end_seconds()) + u64::from(day_offset) * u64::from(SECONDS_PER_DAY);
+    if end_seconds < start_seconds {
+        return Err(range.end_before_start_error());
+    }
+    Ok((end_seconds - start_seconds) as f64 / 3600.0)
+}
+
+/// Calculates the signed difference in hours between two time strings on the same day,
+/// e.g. "5:00PM-9:00AM" gives -8.0 rather than an error. Unlike
+/// `calculate_allowing_overnight`, an earlier end is never moved to the next day, so a start
+/// plus a duration that runs past midnight, such as "10:00PM+4h", is `TimeError::PastMidnight`.
+pub fn signed(range_str: &str) -> Result<f64, TimeError> {
+    let range = resolve(range_str, "-", ImplicitMeridians::default())?;
+    range.check_not_wrapped()?;
+    let start_seconds = range.start.seconds_from_midnight() as f64;
+    let end_seconds = range.end_seconds() as f64;
+    Ok((end_seconds - start_seconds) / 3600.0)
+}
+
+/// Sums the durations, in hours, of several comma-separated ranges such as
+/// "9:00AM-11:00AM,1:00PM-5:00PM". A single range without commas behaves like `calculate`.
+/// The total is kept as a `u64`, so any number of ranges can be summed without overflow.
+pub fn sum(input: &str) -> Result<f64, TimeError> {
+    let mut total_seconds: u64 = 0;
+    for (index, segment) in input.split(',').enumerate() {
+        if segment.trim().is_empty() {
+            return Err(TimeError::InvalidFormat {
+                input: input.to_string(),
+                issue: FormatIssue::EmptyRange { index },
+                position: offset_in(segment, input),
+            });
+        }
+        total_seconds += u64::from(span(segment)?.total_seconds());
+    }
+    Ok(total_seconds as f64 / 3600.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_conversion::Meridian;
+    use alloc::vec;
+
+    #[test]
+    fn test_calculate_difference_explicit_ampm() {
+        assert_eq!(calculate("09:00AM-05:30PM"), Ok(8.5));
+        assert_eq!(calculate("9:00AM-5:30PM"), Ok(8.5));
+    }
+
+    #[test]
+    fn test_calculate_difference_implicit_ampm_range() {
+        assert_eq!(calculate("09:00-05:30"), Ok(8.5));
+        assert_eq!(calculate("9:00-5:30"), Ok(8.5));
+    }
+
+    #[test]
+    fn test_calculate_difference_mixed_ampm_error() {
+        assert!(calculate("09:00AM-05:00").is_err());
+        assert!(calculate("09:00-05:00PM").is_err());
+        assert_eq!(
+            calculate("09:00AM-05:00"),
+            Err(TimeError::AmbiguousRange {
+                input: "09:00AM-05:00".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_calculate_difference_range_format_errors() {
+        assert!(matches!(
+            calculate("09:00AM"),
+            Err(TimeError::InvalidFormat {
+                issue: FormatIssue::RangeSeparator { .. },
+                ..
+            })
+        ));
+        assert!(matches!(
+            calculate("09:00AM- "),
+            Err(TimeError::InvalidFormat {
+                issue: FormatIssue::EmptyRangeSide { .. },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_calculate_difference_end_before_start_error() {
+        let result = calculate("05:00PM-09:00AM");
+        assert!(result.is_err());
+        if let Err(e @ TimeError::EndBeforeStart { .. }) = result {
+            assert!(e.to_string().contains("End time 09:00AM (interpreted as 9:00AM) is before start time 05:00PM (interpreted as 5:00PM)"));
+        }
+    }
+
+    #[test]
+    fn test_calculate_allowing_overnight() {
+        assert_eq!(calculate_allowing_overnight("10:00PM-02:00AM"), Ok(4.0));
+        assert_eq!(calculate_allowing_overnight("11:30PM-12:15AM"), Ok(0.75));
+        assert_eq!(calculate_allowing_overnight("09:00AM-05:30PM"), Ok(8.5));
+        assert_eq!(calculate_allowing_overnight("10:00PM-10:00PM"), Ok(0.0));
+        assert!(calculate("10:00PM-02:00AM").is_err());
+    }
+
+    #[test]
+    fn test_span() {
+        assert_eq!(span("9:00AM-5:30PM"), Ok(TimeSpan::from_minutes(510)));
+        assert_eq!(span("12:00AM-11:59PM").unwrap().to_string(), "23h 59m");
+        assert!(span("05:00PM-09:00AM").is_err());
+    }
+
+    #[test]
+    fn test_calculate_with_seconds() {
+        assert_eq!(calculate("09:00:30AM-09:01:00AM"), Ok(30.0 / 3600.0));
+        assert_eq!(calculate("9:00:00AM-5:30:00PM"), Ok(8.5));
+        assert_eq!(span("9:00AM-9:01:15AM"), Ok(TimeSpan::from_seconds(75)));
+        assert_eq!(calculate_24h("13:00:00-13:00:36"), Ok(0.01));
+        let result = calculate("09:00:30AM-09:00:15AM");
+        if let Err(e @ TimeError::EndBeforeStart { .. }) = result {
+            assert!(e.to_string().contains("(interpreted as 9:00:15AM) is before start time 09:00:30AM (interpreted as 9:00:30AM)"));
+        } else {
+            panic!("expected end-before-start error");
+        }
+    }
+
+    #[test]
+    fn test_calculate_24h() {
+        assert_eq!(calculate_24h("13:00-17:30"), Ok(4.5));
+        assert_eq!(calculate_24h("00:00-23:59"), Ok(1439.0 / 60.0));
+        assert!(calculate_24h("1:00PM-5:00PM").is_err());
+        let result = calculate_24h("17:30-13:00");
+        assert!(result.is_err());
+        if let Err(e @ TimeError::EndBeforeStart { .. }) = result {
+            assert!(e.to_string().contains("End time 13:00 (interpreted as 13:00) is before start time 17:30 (interpreted as 17:30)"));
+        }
+    }
+
+    #[test]
+    fn test_sum() {
+        assert_eq!(sum("9:00AM-11:00AM,1:00PM-5:00PM"), Ok(6.0));
+        assert_eq!(sum("9:00AM-11:00AM, 1:00PM-5:30PM"), Ok(6.5));
+        assert_eq!(sum("9:00AM-5:30PM"), calculate("9:00AM-5:30PM"));
+        assert!(sum("9:00AM-11:00AM,5:00PM-1:00PM").is_err());
+    }
+
+    #[test]
+    fn test_sum_empty_segment_error() {
+        for (input, index) in [
+            ("9:00AM-11:00AM,,1:00PM-5:00PM", 1),
+            (",9:00AM-11:00AM", 0),
+            ("9:00AM-11:00AM, ", 1),
+        ] {
+            match sum(input) {
+                Err(
+                    e @ TimeError::InvalidFormat {
+                        issue: FormatIssue::EmptyRange { .. },
+                        ..
+                    },
+                ) => assert!(e
+                    .to_string()
+                    .contains(&format!("Range at index {} is empty", index))),
+                other => panic!("expected error for '{}', got {:?}", input, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_calculate_with_separator() {
+        assert_eq!(calculate_with_separator("9:00AM to 5:00PM", "to"), Ok(8.0));
+        assert_eq!(
+            calculate_with_separator("9:00AM\u{2013}5:30PM", "\u{2013}"),
+            Ok(8.5)
+        );
+        assert_eq!(
+            calculate_with_separator("9:00-5:30", "-"),
+            calculate("9:00-5:30")
+        );
+        assert!(calculate_with_separator("9:00AM-5:00PM", "to").is_err());
+        assert_eq!(
+            calculate_with_separator("9:00AM to ", "to")
+                .unwrap_err()
+                .to_string(),
+            "Invalid input format: '9:00AM to '. Start or end time string is empty after splitting by 'to'."
+        );
+        assert!(matches!(
+            calculate_with_separator("9:00AM-5:00PM", ""),
+            Err(TimeError::InvalidFormat {
+                issue: FormatIssue::EmptySeparator,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_calculate_worked_hours() {
+        assert_eq!(calculate_worked_hours("9:00AM-5:30PM", 30), Ok(8.0));
+        assert_eq!(calculate_worked_hours("9:00AM-5:30PM", 0), Ok(8.5));
+        assert_eq!(calculate_worked_hours("9:00AM-9:30AM", 30), Ok(0.0));
+        assert_eq!(
+            calculate_worked_hours("9:00AM-9:30AM", 45),
+            Err(TimeError::BreakExceedsSpan {
+                break_minutes: 45,
+                span: TimeSpan::from_minutes(30),
+            })
+        );
+        assert!(calculate_worked_hours("9:00AM-9:30AM", u32::MAX).is_err());
+        assert!(calculate_worked_hours("5:00PM-9:00AM", 0).is_err());
+    }
+
+    #[test]
+    fn test_calculate_with_implicit_meridians() {
+        let pm_pm = ImplicitMeridians {
+            start: Meridian::Pm,
+            end: Meridian::Pm,
+        };
+        assert_eq!(
+            calculate_with_implicit_meridians("1:00-4:00", pm_pm),
+            Ok(3.0)
+        );
+        assert_eq!(
+            calculate_with_implicit_meridians("4:00-9:30", pm_pm),
+            Ok(5.5)
+        );
+        assert!(matches!(
+            calculate_with_implicit_meridians("9:00-5:30", pm_pm),
+            Err(TimeError::EndBeforeStart { .. })
+        ));
+        assert_eq!(
+            calculate_with_implicit_meridians("9:00-5:30", ImplicitMeridians::default()),
+            Ok(8.5)
+        );
+        assert_eq!(
+            calculate_with_implicit_meridians("9:00AM-10:00AM", pm_pm),
+            Ok(1.0)
+        );
+    }
+
+    #[test]
+    fn test_calculate_with_noon_and_midnight() {
+        assert_eq!(calculate("9:00AM-noon"), Ok(3.0));
+        assert_eq!(calculate("midnight-6:00AM"), Ok(6.0));
+        assert_eq!(calculate("Noon-5:30PM"), Ok(5.5));
+        assert_eq!(calculate("9:00-noon"), Ok(3.0));
+        assert_eq!(calculate("noon-5:30"), Ok(5.5));
+        assert_eq!(calculate("midnight-noon"), Ok(12.0));
+        assert!(matches!(
+            calculate("9:00PM-midnight"),
+            Err(TimeError::EndBeforeStart { .. })
+        ));
+        assert_eq!(calculate_allowing_overnight("9:00PM-midnight"), Ok(3.0));
+    }
+
+    #[test]
+    fn test_calculate_range() {
+        let range = calculate_range("9:00-5:30").unwrap();
+        assert_eq!(range.start.format_24h(), "09:00");
+        assert_eq!(range.end.format_24h(), "17:30");
+        assert_eq!(range.span, TimeSpan::from_minutes(510));
+        assert!(calculate_range("5:00PM-9:00AM").is_err());
+    }
+
+    #[test]
+    fn test_calculate_with_spaced_times() {
+        assert_eq!(calculate(" 9 : 00AM - 5 : 30PM "), Ok(8.5));
+        assert_eq!(calculate("9 : 00-5 : 30"), Ok(8.5));
+    }
+
+    #[test]
+    fn test_calculate_rounded() {
+        assert_eq!(
+            calculate_rounded("9:00AM-9:10AM", RoundingMode::CeilQuarter),
+            Ok(0.25)
+        );
+        assert_eq!(
+            calculate_rounded("9:00AM-9:10AM", RoundingMode::NearestQuarter),
+            Ok(0.25)
+        );
+        assert_eq!(
+            calculate_rounded("9:00AM-5:31PM", RoundingMode::NearestQuarter),
+            Ok(8.5)
+        );
+        assert_eq!(
+            calculate_rounded("9:00:00AM-9:00:40AM", RoundingMode::NearestMinute),
+            Ok(1.0 / 60.0)
+        );
+        assert_eq!(
+            calculate_rounded("9:00AM-5:31PM", RoundingMode::Exact),
+            calculate("9:00AM-5:31PM")
+        );
+        assert!(calculate_rounded("5:00PM-9:00AM", RoundingMode::CeilQuarter).is_err());
+    }
+
+    #[test]
+    fn test_calculate_with_unicode_dashes() {
+        for dash in ['\u{2212}', '\u{2013}', '\u{2014}'] {
+            assert_eq!(calculate(&format!("9:00AM{}5:30PM", dash)), Ok(8.5));
+            assert_eq!(calculate(&format!("9:00 {} 5:30", dash)), Ok(8.5));
+            assert_eq!(calculate(&format!("13:00{}17:30", dash)), Ok(4.5));
+            assert!(matches!(
+                calculate(&format!("5:00PM{}9:00AM", dash)),
+                Err(TimeError::EndBeforeStart { .. })
+            ));
+        }
+        // An ASCII '-' is still the separator when both appear.
+        assert!(calculate("9:00AM-5:30PM\u{2013}").is_err());
+        assert!(calculate_with_separator("9:00AM\u{2013}5:30PM", "to").is_err());
+    }
+
+    #[test]
+    fn test_billable_hours() {
+        assert_eq!(billable_hours("9:00AM-9:07AM"), Ok(0.1));
+        assert_eq!(billable_hours("9:00AM-9:02AM"), Ok(0.0));
+        assert_eq!(billable_hours("9:00AM-9:03AM"), Ok(0.1));
+        assert_eq!(billable_hours("9:00AM-5:20PM"), Ok(8.3));
+        assert_eq!(billable_hours("9:00AM-5:30PM"), Ok(8.5));
+        assert!(billable_hours("5:00PM-9:00AM").is_err());
+    }
+
+    #[test]
+    fn test_signed() {
+        assert_eq!(signed("5:00PM-9:00AM"), Ok(-8.0));
+        assert_eq!(signed("9:00AM-5:30PM"), Ok(8.5));
+        assert_eq!(signed("10:00PM-2:00AM"), Ok(-20.0));
+        assert_eq!(signed("10:00AM-10:00AM"), Ok(0.0));
+        assert!(signed("9:00AM-5:00").is_err());
+        assert!(calculate("5:00PM-9:00AM").is_err());
+    }
+
+    #[test]
+    fn test_calculate_input_too_long() {
+        assert_eq!(
+            calculate(&"9".repeat(1000)),
+            Err(TimeError::InputTooLong {
+                length: 1000,
+                max_length: 64,
+            })
+        );
+    }
+
+    #[test]
+    fn test_calculate_from_minutes() {
+        assert_eq!(calculate_from_minutes("540-1050"), Ok(8.5));
+        assert_eq!(calculate_from_minutes("0-1439"), Ok(1439.0 / 60.0));
+        assert_eq!(calculate_from_minutes(" 540 - 570 "), Ok(0.5));
+        assert_eq!(calculate_from_minutes("9:00AM-5:30PM"), Ok(8.5));
+        assert_eq!(
+            calculate_from_minutes("540-1440"),
+            Err(TimeError::InvalidMinuteOfDay {
+                input: "540-1440".to_string(),
+                minutes: "1440".to_string(),
+            })
+        );
+        assert!(matches!(
+            calculate_from_minutes("540-99999999999"),
+            Err(TimeError::InvalidMinuteOfDay { .. })
+        ));
+        let result = calculate_from_minutes("1050-540");
+        if let Err(e @ TimeError::EndBeforeStart { .. }) = result {
+            assert!(e.to_string().contains("End time 540 (interpreted as 09:00) is before start time 1050 (interpreted as 17:30)"));
+        } else {
+            panic!("expected end-before-start error");
+        }
+    }
+
+    #[test]
+    fn test_calculate_with_dotted_meridians() {
+        assert_eq!(calculate("9:00 a.m.-5:30 p.m."), Ok(8.5));
+        assert_eq!(calculate("9:00a.m-5:30p.m"), Ok(8.5));
+    }
+
+    #[test]
+    fn test_calculate_range_minutes() {
+        let range = calculate_range_minutes("9:00-5:30").unwrap();
+        assert_eq!(
+            range,
+            RangeMinutes {
+                start: 540,
+                end: 1050,
+                duration_minutes: 510,
+            }
+        );
+        assert_eq!(range.to_string(), "09:00-17:30 (510m)");
+        assert_eq!(
+            calculate_range_minutes("12:00AM-12:00:59AM")
+                .unwrap()
+                .to_string(),
+            "00:00-00:00 (0m)"
+        );
+        assert!(calculate_range_minutes("5:00PM-9:00AM").is_err());
+    }
+
+    #[test]
+    fn test_calculate_multiday() {
+        assert_eq!(calculate_multiday("9:00AM-5:00AM", 1), Ok(20.0));
+        assert_eq!(calculate_multiday("9:00AM-5:00PM", 2), Ok(56.0));
+        assert_eq!(calculate_multiday("9:00-5:30", 0), calculate("9:00-5:30"));
+        assert!(matches!(
+            calculate_multiday("5:00PM-9:00AM", 0),
+            Err(TimeError::EndBeforeStart { .. })
+        ));
+        assert_eq!(
+            calculate_multiday("12:00AM-12:00AM", u32::MAX),
+            Ok(u32::MAX as f64 * 24.0)
+        );
+    }
+
+    #[test]
+    fn test_fraction_of_workday() {
+        assert_eq!(fraction_of_workday("9:00AM-5:30PM", 8.0), Ok(1.0625));
+        assert_eq!(fraction_of_workday("9:00AM-1:00PM", 8.0), Ok(0.5));
+        assert_eq!(fraction_of_workday("9:00AM-9:00AM", 7.5), Ok(0.0));
+        for workday_hours in [0.0, -8.0, f64::NAN, f64::INFINITY] {
+            assert_eq!(
+                fraction_of_workday("9:00AM-5:30PM", workday_hours),
+                Err(TimeError::InvalidWorkdayHours)
+            );
+        }
+        assert!(fraction_of_workday("5:00PM-9:00AM", 8.0).is_err());
+    }
+
+    #[test]
+    fn test_split_range_uses_first_separator() {
+        assert_eq!(split_range("9:00AM-5:00PM", "-"), Ok(("9:00AM", "5:00PM")));
+        assert_eq!(
+            split_range("9:00AM-5:00PM-6:00PM", "-"),
+            Ok(("9:00AM", "5:00PM-6:00PM"))
+        );
+        assert!(calculate("9:00AM-5:00PM-6:00PM").is_err());
+        assert!(matches!(
+            calculate("9:00AM--5:00PM"),
+            Err(TimeError::InvalidHour { .. })
+        ));
+        assert!(matches!(
+            calculate("-9:00AM"),
+            Err(TimeError::InvalidFormat {
+                issue: FormatIssue::EmptyRangeSide { .. },
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_calculate_range_assumed_meridian() {
+        assert!(calculate_range("9:00-5:30").unwrap().assumed_meridian);
+        assert!(calculate_range("9:00-noon").unwrap().assumed_meridian);
+        assert!(!calculate_range("9:00AM-5:30PM").unwrap().assumed_meridian);
+        assert!(!calculate_range("midnight-noon").unwrap().assumed_meridian);
+        assert_eq!(
+            calculate_range("9:00-5:30").unwrap().span,
+            calculate_range("9:00AM-5:30PM").unwrap().span
+        );
+    }
+
+    #[test]
+    fn test_calculate_decimal_hours() {
+        assert_eq!(calculate_decimal_hours("1.5-3"), Ok(1.5));
+        assert_eq!(calculate_decimal_hours("1.5h-3h"), Ok(1.5));
+        assert_eq!(calculate_decimal_hours("0 - 2.25H"), Ok(2.25));
+        assert_eq!(calculate_decimal_hours(".5h-1"), Ok(0.5));
+        for bad in ["1.5x-3", "1.5-inf", "1.2.3-4", "h-3", "1e2-300", "NaN-1"] {
+            assert!(
+                matches!(
+                    calculate_decimal_hours(bad),
+                    Err(TimeError::InvalidDecimalHours { .. })
+                ),
+                "expected invalid decimal hours for '{}'",
+                bad
+            );
+        }
+        assert!(matches!(
+            calculate_decimal_hours("--1-3"),
+            Err(TimeError::InvalidFormat { .. })
+        ));
+        assert_eq!(
+            calculate_decimal_hours("3h-1.5h").unwrap_err().to_string(),
+            "End time 1.5h (interpreted as 1.5 hours) is before start time 3h (interpreted as 3 hours). The range must be within a single day and end time must be after start time."
+        );
+    }
+
+    #[test]
+    fn test_hours_and_minutes() {
+        assert_eq!(hours_and_minutes("9:00AM-5:30PM"), Ok((8, 30)));
+        assert_eq!(hours_and_minutes("9:00AM-9:20AM"), Ok((0, 20)));
+        assert_eq!(hours_and_minutes("12:00AM-11:59PM"), Ok((23, 59)));
+        assert_eq!(hours_and_minutes("9:00:00AM-9:20:59AM"), Ok((0, 20)));
+        assert!(hours_and_minutes("5:00PM-9:00AM").is_err());
+    }
+
+    #[test]
+    fn test_calculate_24h_end_of_day() {
+        assert_eq!(calculate_24h("00:00-24:00"), Ok(24.0));
+        assert_eq!(calculate_24h("22:30-24:00"), Ok(1.5));
+        assert_eq!(calculate_24h("23:59:30-24:00:00"), Ok(30.0 / 3600.0));
+        assert!(matches!(
+            calculate_24h("24:00-24:00"),
+            Err(TimeError::InvalidHour { .. })
+        ));
+        assert!(matches!(
+            calculate_24h("24:00-23:00"),
+            Err(TimeError::InvalidHour { .. })
+        ));
+        assert!(matches!(
+            calculate_24h("00:00-24:30"),
+            Err(TimeError::InvalidHour { .. })
+        ));
+        assert!(calculate_24h("00:00-24:00:01").is_err());
+    }
+
+    #[test]
+    fn test_input_too_long_for_every_entry_point() {
+        let long_range = format!("9:00AM-{}", "5".repeat(100));
+        let long_minutes = format!("540-{}", "1".repeat(100));
+        let long_decimal = format!("1.5-{}", "2".repeat(100));
+        assert!(long_range.len() > DEFAULT_MAX_INPUT_LENGTH);
+        let is_too_long = |error: TimeError| matches!(error, TimeError::InputTooLong { .. });
+        assert!(is_too_long(span(&long_range).unwrap_err()));
+        assert!(is_too_long(sum(&long_range).unwrap_err()));
+        assert!(is_too_long(calculate_range(&long_range).unwrap_err()));
+        assert!(is_too_long(signed(&long_range).unwrap_err()));
+        assert!(is_too_long(calculate_24h(&long_range).unwrap_err()));
+        assert!(is_too_long(
+            calculate_from_minutes(&long_minutes).unwrap_err()
+        ));
+        assert!(is_too_long(
+            calculate_decimal_hours(&long_decimal).unwrap_err()
+        ));
+        assert!(is_too_long(
+            crate::calculate_with_offsets(&long_range).unwrap_err()
+        ));
+        assert!(is_too_long(
+            crate::calculate_with_dates(&long_range).unwrap_err()
+        ));
+        assert!(is_too_long(
+            crate::normalize_range(&long_range).unwrap_err()
+        ));
+        assert!(is_too_long(
+            calculate_range(&format!("9:00AM+{}h", "1".repeat(100))).unwrap_err()
+        ));
+        // A calculator with a higher limit still accepts a range the default would reject.
+        let padded = format!("9:00AM-5:30PM{}", " ".repeat(100));
+        assert!(is_too_long(calculate(&padded).unwrap_err()));
+        assert_eq!(
+            DurationCalculator::new()
+                .max_input_length(200)
+                .calculate(&padded),
+            Ok(8.5)
+        );
+    }
+
+    #[test]
+    fn test_calculate_range_end_of_day() {
+        let range = calculate_range("22:00-24:00").unwrap();
+        assert_eq!(range.start, TimeOfDay::from_hms(22, 0, 0).unwrap());
+        assert_eq!(range.end, TimeOfDay::MIDNIGHT);
+        assert!(range.end_of_day);
+        assert_eq!(range.end_seconds(), SECONDS_PER_DAY);
+        assert_eq!(range.span, TimeSpan::from_minutes(120));
+        assert_eq!(calculate_range_allowing_overnight("22:00-24:00"), Ok(range));
+        assert_eq!(count_minutes_inclusive("22:00-24:00"), Ok(120));
+        assert_eq!(calculate_snapped("22:07-24:00", 15), Ok(2.0));
+        assert_eq!(
+            calculate_range_minutes("22:00-24:00").unwrap().to_string(),
+            "22:00-24:00 (120m)"
+        );
+        assert!(!calculate_range("22:00-23:00").unwrap().end_of_day);
+    }
+
+    #[test]
+    fn test_calculate_auto_24h_end_of_day() {
+        assert_eq!(calculate("13:00-24:00"), Ok(11.0));
+        assert_eq!(calculate("22:00-24:00"), Ok(2.0));
+        assert_eq!(calculate("00:00-24:00"), Ok(24.0));
+        assert_eq!(calculate("9:00-24:00"), Ok(15.0));
+        assert_eq!(calculate("22:00-24:00:00"), calculate_24h("22:00-24:00:00"));
+        assert_eq!(span("22:00-24:00"), Ok(TimeSpan::from_minutes(120)));
+        assert_eq!(signed("22:00-24:00"), Ok(2.0));
+        assert_eq!(calculate_multiday("22:00-24:00", 1), Ok(26.0));
+        assert_eq!(calculate_allowing_overnight("22:00-24:00"), Ok(2.0));
+        for range_str in ["13:00-24:00", "22:00-24:00", "00:00-24:00", "9:00-24:00"] {
+            assert_eq!(
+                calculate_range(range_str).map(|range| range.span.as_hours_f64()),
+                calculate(range_str)
+            );
+        }
+        assert!(matches!(
+            calculate("9:00PM-24:00"),
+            Err(TimeError::InvalidHour { .. })
+        ));
+        let calculator = DurationCalculator::new()
+            .reject_zero_duration(true)
+            .day_start(TimeOfDay::from_hms(4, 0, 0).unwrap());
+        assert_eq!(calculator.calculate("05:00-24:00"), Ok(19.0));
+        assert_eq!(
+            calculator.calculate("02:00-24:00"),
+            Err(TimeError::EndBeforeStart {
+                start: "02:00".to_string(),
+                start_interpreted: "02:00".to_string(),
+                start_minutes: 120,
+                end: "24:00".to_string(),
+                end_interpreted: "24:00".to_string(),
+                end_minutes: 1440,
+            })
+        );
+        assert_eq!(
+            DurationCalculator::new()
+                .reject_zero_duration(true)
+                .calculate("00:00-24:00"),
+            Ok(24.0)
+        );
+    }
+
+    #[test]
+    fn test_sum_many_ranges_does_not_overflow() {
+        let ranges = 60_000;
+        let input = vec!["12:00AM-11:59PM"; ranges].join(",");
+        assert_eq!(sum(&input), Ok(ranges as f64 * (23.0 + 59.0 / 60.0)));
+    }
+
+    #[test]
+    fn test_to_std_duration() {
+        assert_eq!(
+            to_std_duration("9:00AM-5:30PM"),
+            Ok(core::time::Duration::from_secs(30600))
+        );
+        assert_eq!(
+            to_std_duration("9:00:15AM-9:00:45AM"),
+            Ok(core::time::Duration::from_secs(30))
+        );
+        assert!(to_std_duration("5:30PM-9:00AM").is_err());
+    }
+
+    #[test]
+    fn test_calculate_range_allowing_overnight() {
+        let range = calculate_range_allowing_overnight("10:00PM-2:00AM").unwrap();
+        assert_eq!(range.start, TimeOfDay::from_hms(22, 0, 0).unwrap());
+        assert_eq!(range.end, TimeOfDay::from_hms(2, 0, 0).unwrap());
+        assert_eq!(range.span, TimeSpan::from_minutes(240));
+        assert_eq!(
+            calculate_range_allowing_overnight("9:00-5:30").map(|range| range.span),
+            calculate_range("9:00-5:30").map(|range| range.span)
+        );
+    }
+
+    #[test]
+    fn test_validate() {
+        assert_eq!(validate("9:00AM-5:30PM"), Ok(()));
+        assert_eq!(validate("9:00-5:30"), Ok(()));
+        assert!(matches!(
+            validate("5:30PM-9:00AM"),
+            Err(TimeError::EndBeforeStart { .. })
+        ));
+        assert!(matches!(
+            validate("9:00AM-5:30"),
+            Err(TimeError::AmbiguousRange { .. })
+        ));
+        assert!(validate("9:60AM-5:30PM").is_err());
+    }
+
+    #[test]
+    fn test_count_minutes_inclusive() {
+        assert_eq!(count_minutes_inclusive("9:00AM-9:00AM"), Ok(1));
+        assert_eq!(count_minutes_inclusive("9:00AM-9:59AM"), Ok(60));
+        assert_eq!(count_minutes_inclusive("9:00-5:30"), Ok(511));
+        assert_eq!(count_minutes_inclusive("9:00:45AM-9:01:10AM"), Ok(2));
+        assert_eq!(count_minutes_inclusive("12:00AM-11:59PM"), Ok(1440));
+        assert!(count_minutes_inclusive("9:01AM-9:00AM").is_err());
+        assert_eq!(calculate("9:00AM-9:00AM"), Ok(0.0));
+    }
+
+    #[test]
+    fn test_calculate_detects_24_hour_ranges() {
+        assert_eq!(calculate("13:00-17:30"), Ok(4.5));
+        assert_eq!(calculate("9:00-13:00"), Ok(4.0));
+        assert_eq!(calculate("09:00-05:30"), Ok(8.5));
+        assert!(matches!(
+            calculate("17:30-13:00"),
+            Err(TimeError::EndBeforeStart { end_interpreted, .. }) if end_interpreted == "13:00"
+        ));
+        assert!(calculate("13:00-5:30PM").is_err());
+        let range = calculate_range("13:00-17:30").unwrap();
+        assert_eq!(range.start, TimeOfDay::from_hms(13, 0, 0).unwrap());
+        assert!(!range.assumed_meridian);
+    }
+
+    #[test]
+    fn test_calculate_detects_24_hour_ranges_with_leading_zeros() {
+        assert_eq!(calculate("07:00-19:30"), Ok(12.5));
+        assert_eq!(calculate("7:00-19:30"), Ok(12.5));
+        assert_eq!(calculate("00:30-19:30"), Ok(19.0));
+        assert!(matches!(
+            calculate("19:30-00:30"),
+            Err(TimeError::EndBeforeStart {
+                end_minutes: 30,
+                ..
+            })
+        ));
+        assert!(!calculate_range("07:00-19:30").unwrap().assumed_meridian);
+
+        // Hours of 1-12 are still read with the implicit AM/PM: 7:00AM-9:30PM.
+        assert_eq!(calculate("07:00-09:30"), Ok(14.5));
+        assert_eq!(calculate("07:00-09:30"), calculate("7:00AM-9:30PM"));
+        assert!(calculate_range("07:00-09:30").unwrap().assumed_meridian);
+        assert!(calculate("00:30AM-9:30AM").is_err());
+
+        // Hour 0 alone does not switch to 24-hour, so it is rejected as a 12-hour hour.
+        assert!(matches!(
+            calculate("00:30-09:30"),
+            Err(TimeError::InvalidHour { .. })
+        ));
+        assert!(calculate("0:00-06:15").is_err());
+    }
+
+    #[test]
+    fn test_check_single_day() {
+        assert_eq!(
+            check_single_day(TimeSpan::from_minutes(510)),
+            Ok(TimeSpan::from_minutes(510))
+        );
+        assert_eq!(
+            check_single_day(TimeSpan::from_minutes(1440)),
+            Ok(TimeSpan::from_minutes(1440))
+        );
+        assert_eq!(
+            check_single_day(TimeSpan::from_minutes(1441)),
+            Err(TimeError::SpanExceedsDay {
+                span: TimeSpan::from_minutes(1441)
+            })
+        );
+        assert_eq!(calculate("12:00AM-11:59:59PM"), Ok(86399.0 / 3600.0));
+        assert_eq!(calculate_24h("00:00-24:00"), Ok(24.0));
+    }
+
+    #[test]
+    fn test_start_plus_duration() {
+        let range = calculate_range("9:00AM+8h").unwrap();
+        assert_eq!(range.end, TimeOfDay::parse("5:00PM").unwrap());
+        assert_eq!(range.span, TimeSpan::from_minutes(480));
+        assert!(!range.assumed_meridian);
+        assert_eq!(calculate("9:00AM + 8h 30m"), Ok(8.5));
+        assert_eq!(calculate("1:15PM+45m"), Ok(0.75));
+        assert!(calculate_range("9:00+8h").unwrap().assumed_meridian);
+        assert_eq!(
+            calculate("10:00PM+4h"),
+            Err(TimeError::PastMidnight {
+                start: "10:00PM".to_string(),
+                minutes: 240,
+            })
+        );
+        assert_eq!(calculate_allowing_overnight("10:00PM+4h"), Ok(4.0));
+        assert_eq!(
+            calculate_range_allowing_overnight("10:00PM+4h")
+                .unwrap()
+                .end,
+            TimeOfDay::parse("2:00AM").unwrap()
+        );
+        assert!(calculate_allowing_overnight("9:00AM+24h").is_err());
+        assert!(calculate("9:00AM+8").is_err());
+        assert!(calculate("9:00AM+").is_err());
+        assert!(calculate("13:00PM+1h").is_err());
+        assert!(calculate("9:00AM+1h-5:00PM").is_err());
+    }
+
+    #[test]
+    fn test_start_plus_duration_past_midnight() {
+        let past_midnight = Err(TimeError::PastMidnight {
+            start: "10:00PM".to_string(),
+            minutes: 240,
+        });
+        assert_eq!(signed("10:00PM+4h"), past_midnight);
+        assert_eq!(calculate_multiday("10:00PM+4h", 0), past_midnight);
+        assert_eq!(calculate_multiday("10:00PM+4h", 3), past_midnight);
+        assert_eq!(signed("9:00AM+8h"), Ok(8.0));
+        assert_eq!(calculate_multiday("9:00AM+8h", 3), Ok(80.0));
+        assert_eq!(signed("10:00PM+1h"), Ok(1.0));
+    }
+
+    #[test]
+    fn test_is_ambiguous() {
+        assert_eq!(is_ambiguous("9:00AM-5:30"), Ok(true));
+        assert_eq!(is_ambiguous("9:00-5:30PM"), Ok(true));
+        assert_eq!(is_ambiguous("9:00AM-5:30PM"), Ok(false));
+        assert_eq!(is_ambiguous("9:00-5:30"), Ok(false));
+        assert_eq!(is_ambiguous("noon-5:30"), Ok(false));
+        assert_eq!(is_ambiguous("13:00-17:30"), Ok(false));
+        assert_eq!(is_ambiguous("5:00PM-9:00"), Ok(true));
+        assert_eq!(is_ambiguous("5:00PM-9:00AM"), Ok(false));
+        assert!(is_ambiguous("9:00AM-5:3x").is_err());
+        assert!(is_ambiguous("9:00AM").is_err());
+        assert!(matches!(
+            calculate("9:00AM-5:30"),
+            Err(TimeError::AmbiguousRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_calculate_snapped() {
+        assert_eq!(calculate_snapped("9:07AM-4:53PM", 15), Ok(8.0));
+        assert_eq!(calculate_snapped("9:07AM-4:52PM", 15), Ok(7.75));
+        assert_eq!(calculate_snapped("9:08AM-4:53PM", 15), Ok(7.75));
+        assert_eq!(calculate_snapped("9:15AM-5:15PM", 30), Ok(8.0));
+        assert_eq!(calculate_snapped("9:07:59AM-5:00PM", 15), Ok(8.0));
+        assert_eq!(calculate_snapped("9:00AM-9:05AM", 15), Ok(0.0));
+        assert_eq!(calculate_snapped("9:00AM-5:30PM", 60), Ok(9.0));
+        assert_eq!(calculate_snapped("9:07AM-4:52PM", 1), Ok(7.75));
+        assert_eq!(calculate_snapped("11:00PM-11:53PM", 15), Ok(1.0));
+        assert_eq!(
+            calculate_snapped("9:00AM-5:00PM", 0),
+            Err(TimeError::InvalidStep { step_minutes: 0 })
+        );
+        assert!(matches!(
+            calculate_snapped("5:00PM-9:00AM", 15),
+            Err(TimeError::EndBeforeStart { .. })
+        ));
+    }
+
+    #[test]
+    fn test_end_before_start_minutes() {
+        assert_eq!(
+            calculate("5:30PM-9:00AM"),
+            Err(TimeError::EndBeforeStart {
+                start: "5:30PM".to_string(),
+                start_interpreted: "5:30PM".to_string(),
+                start_minutes: 1050,
+                end: "9:00AM".to_string(),
+                end_interpreted: "9:00AM".to_string(),
+                end_minutes: 540,
+            })
+        );
+        assert!(matches!(
+            calculate_24h("17:30-09:15:30"),
+            Err(TimeError::EndBeforeStart {
+                start_minutes: 1050,
+                end_minutes: 555,
+                ..
+            })
+        ));
+        assert!(matches!(
+            calculate_decimal_hours("3-1.5h"),
+            Err(TimeError::EndBeforeStart {
+                start_minutes: 180,
+                end_minutes: 90,
+                ..
+            })
+        ));
+        assert_eq!(
+            calculate("5:30PM-9:00AM").unwrap_err().to_string(),
+            "End time 9:00AM (interpreted as 9:00AM) is before start time 5:30PM (interpreted as 5:30PM). The range must be within a single day and end time must be after start time."
+        );
+    }
+}
@@ -1,249 +1,690 @@
-/// Represents an error that can occur during time parsing or calculation.
-#[derive(Debug, PartialEq)]
-struct TimeError(String);
+use std::env;
+use std::io::{self, IsTerminal};
+use std::process;
+use time_duration_calculation::{
+    calculate_range, calculate_range_allowing_overnight, format_iso8601, hours_since,
+    parse_time_components, process_range_lines_with, DurationAccumulator, RangeCalculation,
+    TimeError, TimeOfDay, DEFAULT_MAX_INPUT_LENGTH,
+};
 
-impl std::fmt::Display for TimeError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
-    }
+/// Returns the usage text printed when the arguments are not understood.
+fn usage(program_name: &str) -> String {
+    let mut lines = Vec::new();
+    lines.push("Calculates the difference in hours between two times in a day.".to_string());
+    lines.push("Usage:".to_string());
+    lines.push(format!(
+        "  1. Time range: {} \"H(H):MM[:SS][am/pm]-H(H):MM[:SS][am/pm]\"",
+        program_name
+    ));
+    lines.push(format!(
+        "     Example: {} \"09:00AM-05:30PM\"",
+        program_name
+    ));
+    lines.push(format!(
+        "     Example (start and end as separate arguments): {} 09:00AM 05:30PM",
+        program_name
+    ));
+    lines.push(format!(
+        "     Example (implicit AM/PM for range): {} \"9:00-5:30\" (interprets as 9:00AM-5:30PM)",
+        program_name
+    ));
+    lines.push(format!(
+        "     Example (start and duration): {} \"9:00AM+8h30m\"",
+        program_name
+    ));
+    lines.push(format!(
+        "  2. Single time (start time assumed AM, end time is current system time): {} \"H(H):MM\"",
+        program_name
+    ));
+    lines.push(format!(
+        "     Example: {} \"09:15\" (interprets as 09:15AM - CurrentSystemTime)",
+        program_name
+    ));
+    lines.push(format!(
+        "  3. Batch (one range per line on stdin, one result per line on stdout, then a total on stderr): cat ranges.txt | {}",
+        program_name
+    ));
+    lines.push(format!(
+        "  4. Check a single time (AM assumed if omitted) and print it in 24-hour form: {} --check \"9:00PM\"",
+        program_name
+    ));
+    lines.push(format!(
+        "  5. Time elapsed since a time today (AM assumed if omitted): {} --since 9:00AM",
+        program_name
+    ));
+    lines.push("Options:".to_string());
+    lines.push("  --quiet    Print errors on one line, without this text or notes about how input was read".to_string());
+    lines
+        .push("  --minutes  Print the total duration in minutes, e.g. \"510 minutes\"".to_string());
+    lines.push("  --overnight  Read an end before the start as the next day, e.g. \"10:00PM-2:00AM\" is 4 hours".to_string());
+    lines.push(format!(
+        "  --decimals N  Print hours with N decimal places (0-{}), e.g. \"8.50 hours\"; -v defaults to 2",
+        MAX_DECIMALS
+    ));
+    lines.push("  --decimal-comma  Write hours with a decimal comma and '.' between thousands, e.g. \"1.234,50 hours\"; --json and --csv keep a dot".to_string());
+    lines.push("  --raw      Print just the number, e.g. \"8.50\" (or \"510\" with --minutes), for use in scripts".to_string());
+    lines.push(
+        "  --iso      Print the duration as an ISO-8601 duration, e.g. \"PT8H30M\"".to_string(),
+    );
+    lines.push("  --warn-implicit  Warn on stderr when a range relies on the assumed AM/PM, e.g. \"9:00-5:30\"".to_string());
+    lines.push("  -v         Show the interpreted 24-hour start and end, e.g. \"09:00 \u{2192} 17:30 = 8.50 hours\"".to_string());
+    lines.push("  --json     Print {\"start\",\"end\",\"hours\",\"minutes\"} as JSON; errors print {\"error\"}".to_string());
+    lines.push("  --csv      Print start,end,hours,minutes as a CSV row, e.g. \"09:00AM,05:30PM,8.50,510\"".to_string());
+    lines.push(format!(
+        "  --csv-header  Like --csv, with a \"{}\" header row first",
+        CSV_HEADER
+    ));
+    lines.push("  --format T Print the template T with {start}, {end}, {hours}, {minutes} and {iso} filled in,".to_string());
+    lines.push(
+        "             e.g. \"{start} to {end} = {hours}h\"; write {{ and }} for literal braces"
+            .to_string(),
+    );
+    lines.join("\n")
 }
 
-impl std::error::Error for TimeError {}
-
-mod time_parsing {
-    use super::TimeError;
-
-    /// Parses a time string (e.g., "9:00AM", "09:00", "10:30PM") into its components.
-    /// Returns (hour, minute, Option<AM/PM>)
-    pub fn parse_time_components(time_str: &str) -> Result<(u32, u32, Option<String>), TimeError> {
-        let original_time_str = time_str;
-        let mut time_part = time_str.trim();
-        let mut ampm_opt: Option<String> = None;
-
-        // Check for AM/PM suffix (case-insensitive)
-        if time_part.len() >= 2 {
-            let potential_ampm = &time_part[time_part.len() - 2..];
-            if potential_ampm.eq_ignore_ascii_case("AM")
-                || potential_ampm.eq_ignore_ascii_case("PM")
-            {
-                if time_part.len() > 2 {
-                    let char_before_ampm = time_part.chars().nth(time_part.len() - 3);
-                    if char_before_ampm.map_or(false, |c| c.is_alphabetic()) {
-                        // Not a valid AM/PM marker
-                    } else {
-                        ampm_opt = Some(potential_ampm.to_uppercase());
-                        time_part = &time_part[..time_part.len() - 2];
-                    }
-                } else {
-                    if potential_ampm.len() == time_part.len() {
-                        return Err(TimeError(format!(
-                            "Invalid time format: '{}'. Time string is too short or just an AM/PM indicator.",
-                            original_time_str
-                        )));
+/// The largest accepted value for `--decimals`.
+const MAX_DECIMALS: usize = 10;
+
+/// The header row printed by `--csv-header`, naming the columns of a `--csv` row.
+const CSV_HEADER: &str = "start,end,hours,minutes";
+
+/// How results and errors are written to the terminal. The default is the plain
+/// "8h 30m" output with every flag off.
+#[derive(Default)]
+struct OutputOptions {
+    print_minutes: bool,
+    json: bool,
+    /// Print a CSV row with the `CSV_HEADER` columns, hours to `--decimals` places or 2.
+    csv: bool,
+    verbose: bool,
+    /// Print only the number, with no unit or interpretation.
+    raw: bool,
+    /// Print the duration as ISO-8601, e.g. "PT8H30M".
+    iso: bool,
+    /// Report on stderr when an implicit AM/PM was applied to a range.
+    warn_implicit: bool,
+    /// Write hours with a decimal comma, e.g. "8,50"; JSON and CSV always use a dot.
+    decimal_comma: bool,
+    /// Decimal places for hours; when unset, hours are only shown by `-v` and `--raw`, with 2 places.
+    decimals: Option<usize>,
+    /// The parsed `--format` template, which replaces every other output style but JSON.
+    template: Option<Vec<TemplatePart>>,
+}
+
+/// A piece of a `--format` template: literal text or a placeholder for part of the result.
+#[derive(Debug, PartialEq)]
+enum TemplatePart {
+    Literal(String),
+    Start,
+    End,
+    Hours,
+    Minutes,
+    Iso,
+}
+
+/// Parses a `--format` template such as "{start} to {end} = {hours}h". Unknown placeholders
+/// and unmatched braces are errors, so a typo is reported before any input is read.
+fn parse_template(template: &str) -> Result<Vec<TemplatePart>, String> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut chars = template.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.as_str().starts_with('{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.as_str().starts_with('}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '{' => {
+                let rest = chars.as_str();
+                let Some(end) = rest.find('}') else {
+                    return Err(format!("--format has an unclosed '{{' in '{}'.", template));
+                };
+                let part = match &rest[..end] {
+                    "start" => TemplatePart::Start,
+                    "end" => TemplatePart::End,
+                    "hours" => TemplatePart::Hours,
+                    "minutes" => TemplatePart::Minutes,
+                    "iso" => TemplatePart::Iso,
+                    name => {
+                        return Err(format!(
+                            "--format has an unknown placeholder '{{{}}}'. Use {{start}}, {{end}}, {{hours}}, {{minutes}} or {{iso}}.",
+                            name
+                        ))
                     }
-                    ampm_opt = Some(potential_ampm.to_uppercase());
-                    time_part = &time_part[..time_part.len() - 2];
+                };
+                if !literal.is_empty() {
+                    parts.push(TemplatePart::Literal(std::mem::take(&mut literal)));
                 }
+                parts.push(part);
+                chars = rest[end + 1..].chars();
             }
+            '}' => return Err(format!("--format has an unmatched '}}' in '{}'.", template)),
+            c => literal.push(c),
         }
+    }
+    if !literal.is_empty() {
+        parts.push(TemplatePart::Literal(literal));
+    }
+    Ok(parts)
+}
 
-        let parts: Vec<&str> = time_part.split(':').collect();
-        if parts.len() != 2 {
-            return Err(TimeError(format!(
-                "Invalid time format: '{}'. Expected H:MM or HH:MM (optionally followed by AM/PM). Missing or too many colons.",
-                original_time_str
-            )));
-        }
+/// Fills in a parsed `--format` template for `range`. Hours use `--decimals`, or 2 places.
+fn render_template(
+    parts: &[TemplatePart],
+    range: &RangeCalculation,
+    options: &OutputOptions,
+) -> String {
+    let span = range.span;
+    parts
+        .iter()
+        .map(|part| match part {
+            TemplatePart::Literal(text) => text.clone(),
+            TemplatePart::Start => range.start.to_string(),
+            TemplatePart::End => format_end(range),
+            TemplatePart::Hours => format_hours(span.as_hours_f64(), options, false),
+            TemplatePart::Minutes => span.total_minutes().to_string(),
+            TemplatePart::Iso => format_iso8601(span),
+        })
+        .collect()
+}
 
-        let h_str = parts[0];
-        let m_str = parts[1];
+/// Parses the value given to `--decimals`, which must be a whole number from 0 to `MAX_DECIMALS`.
+fn parse_decimals(value: Option<&str>) -> Result<usize, String> {
+    value
+        .and_then(|v| v.parse::<usize>().ok())
+        .filter(|n| *n <= MAX_DECIMALS)
+        .ok_or_else(|| {
+            format!(
+                "--decimals expects a whole number between 0 and {}, got '{}'.",
+                MAX_DECIMALS,
+                value.unwrap_or("")
+            )
+        })
+}
 
-        if h_str.is_empty() || !(1..=2).contains(&h_str.len()) {
-            return Err(TimeError(format!(
-                "Invalid hour format in '{}'. Hour part '{}' must be 1 or 2 digits.",
-                original_time_str, h_str
-            )));
-        }
-        if m_str.len() != 2 {
-            return Err(TimeError(format!(
-                "Invalid minute format in '{}'. Minute part '{}' must be 2 digits.",
-                original_time_str, m_str
-            )));
+/// Escapes `s` as a JSON string literal, including the surrounding quotes.
+fn json_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
         }
+    }
+    escaped.push('"');
+    escaped
+}
 
-        let hour12: u32 = h_str.parse().map_err(|_| {
-            TimeError(format!(
-                "Invalid hour value: '{}' in '{}'. Hour must be a number.",
-                h_str, original_time_str
-            ))
-        })?;
-        let minute: u32 = m_str.parse().map_err(|_| {
-            TimeError(format!(
-                "Invalid minute value: '{}' in '{}'. Minute must be a number.",
-                m_str, original_time_str
-            ))
-        })?;
+/// Quotes `field` for CSV if it contains a comma, quote or line break, doubling any quotes.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
 
-        if !(1..=12).contains(&hour12) {
-            return Err(TimeError(format!(
-                "Invalid hour: {}. Hour must be between 1 and 12 for 12-hour format in '{}'.",
-                hour12, original_time_str
-            )));
-        }
-        if minute > 59 {
-            return Err(TimeError(format!(
-                "Invalid minute: {}. Minute must be between 0 and 59 in '{}'.",
-                minute, original_time_str
-            )));
+/// Formats `hours` to `--decimals` places, or 2, with a decimal comma for `--decimal-comma`.
+/// With `grouped`, thousands are separated as by `localize_number`.
+fn format_hours(hours: f64, options: &OutputOptions, grouped: bool) -> String {
+    localize_number(
+        &format!("{:.*}", options.decimals.unwrap_or(2), hours),
+        options,
+        grouped,
+    )
+}
+
+/// Formats a whole number of minutes for people, e.g. "123,456".
+fn format_minutes(minutes: u64, options: &OutputOptions) -> String {
+    localize_number(&minutes.to_string(), options, true)
+}
+
+/// Rewrites a plain non-negative number such as "123456.50" for the chosen locale: with a
+/// decimal comma for `--decimal-comma`, and with `grouped`, thousands separated by ',' or,
+/// alongside a decimal comma, by '.', giving "123,456.50" or "123.456,50".
+fn localize_number(number: &str, options: &OutputOptions, grouped: bool) -> String {
+    let (integer, fraction) = match number.split_once('.') {
+        Some((integer, fraction)) => (integer, Some(fraction)),
+        None => (number, None),
+    };
+    let (group_separator, decimal_separator) = if options.decimal_comma {
+        ('.', ',')
+    } else {
+        (',', '.')
+    };
+    let mut localized = String::with_capacity(number.len() + integer.len() / 3);
+    for (index, digit) in integer.chars().enumerate() {
+        if grouped && index > 0 && (integer.len() - index) % 3 == 0 {
+            localized.push(group_separator);
         }
+        localized.push(digit);
+    }
+    if let Some(fraction) = fraction {
+        localized.push(decimal_separator);
+        localized.push_str(fraction);
+    }
+    localized
+}
 
-        Ok((hour12, minute, ampm_opt))
+/// Returns whether `format_result` would print only the hours, with `--raw` or `--decimals`,
+/// so that a result can be given without its range.
+fn prints_only_hours(options: &OutputOptions) -> bool {
+    if options.json || options.csv || options.template.is_some() {
+        return false;
+    }
+    if options.raw {
+        return !options.print_minutes;
     }
+    !options.iso && !options.verbose && !options.print_minutes && options.decimals.is_some()
 }
 
-mod time_conversion {
-    use super::TimeError;
-    /// Converts 12-hour format components (hour, minute, AM/PM) into total minutes from midnight.
-    pub fn to_minutes(
-        hour12: u32,
-        minute: u32,
-        ampm_indicator: &str,
-        original_time_str: &str,
-    ) -> Result<u32, TimeError> {
-        let mut hour24 = hour12;
-        match ampm_indicator {
-            "AM" => if hour12 == 12 { hour24 = 0; },
-            "PM" => if hour12 != 12 { hour24 += 12; },
-            _ => return Err(TimeError(format!(
-                "Internal error or invalid AM/PM indicator: '{}' for time '{}'. Expected 'AM' or 'PM'.",
-                ampm_indicator, original_time_str
-            ))),
-        }
-        Ok(hour24 * 60 + minute)
+/// Formats the end of a range in canonical form, or as "24:00" for the end of the day, which
+/// has no 12-hour form.
+fn format_end(range: &RangeCalculation) -> String {
+    if range.end_of_day {
+        "24:00".to_string()
+    } else {
+        range.end.to_string()
     }
 }
 
-mod time_difference {
-    use super::{time_conversion::to_minutes, time_parsing::parse_time_components, TimeError};
-    /// Calculates the difference in hours between two time strings.
-    pub fn calculate(range_str: &str) -> Result<f64, TimeError> {
-        let parts: Vec<&str> = range_str.split('-').collect();
-        if parts.len() != 2 {
-            return Err(TimeError(format!(
-                "Invalid input format: '{}'. Expected format is H(H):MM[am/pm]-H(H):MM[am/pm].",
-                range_str
-            )));
-        }
-        let raw_start = parts[0].trim();
-        let raw_end = parts[1].trim();
-        if raw_start.is_empty() || raw_end.is_empty() {
-            return Err(TimeError(format!(
-                "Invalid input format: '{}'. Start or end time string is empty after splitting by '-'.",
-                range_str
-            )));
+/// Formats the result of a successfully calculated range.
+fn format_result(range: &RangeCalculation, options: &OutputOptions) -> String {
+    let span = range.span;
+    if options.json {
+        format!(
+            "{{\"start\":{},\"end\":{},\"hours\":{},\"minutes\":{}}}",
+            json_string(&range.start.to_string()),
+            json_string(&format_end(range)),
+            span.as_hours_f64(),
+            span.total_minutes()
+        )
+    } else if options.csv {
+        format!(
+            "{},{},{:.*},{}",
+            csv_field(&range.start.to_string()),
+            csv_field(&format_end(range)),
+            options.decimals.unwrap_or(2),
+            span.as_hours_f64(),
+            span.total_minutes()
+        )
+    } else if let Some(template) = &options.template {
+        render_template(template, range, options)
+    } else if options.raw {
+        if options.print_minutes {
+            span.total_minutes().to_string()
+        } else {
+            format_hours(span.as_hours_f64(), options, false)
         }
-        let (start_h, start_m, start_ampm) = parse_time_components(raw_start)?;
-        let (end_h, end_m, end_ampm) = parse_time_components(raw_end)?;
-        let (start_minutes, end_minutes, start_ampm_str, end_ampm_str) = match (start_ampm, end_ampm) {
-            (Some(s), Some(e)) => (
-                to_minutes(start_h, start_m, &s, raw_start)?,
-                to_minutes(end_h, end_m, &e, raw_end)?,
-                s, e
-            ),
-            (None, None) => (
-                to_minutes(start_h, start_m, "AM", raw_start)?,
-                to_minutes(end_h, end_m, "PM", raw_end)?,
-                "AM".to_string(), "PM".to_string()
-            ),
-            _ => return Err(TimeError(format!(
-                "Ambiguous time range: '{}'. Both times must specify AM/PM, or neither should. If neither, start is assumed AM and end is assumed PM.",
-                range_str
-            ))),
+    } else if options.iso {
+        format_iso8601(span)
+    } else if options.verbose {
+        let total = if options.print_minutes {
+            format!(
+                "{} minutes",
+                format_minutes(u64::from(span.total_minutes()), options)
+            )
+        } else {
+            format!("{} hours", format_hours(span.as_hours_f64(), options, true))
         };
-        if end_minutes < start_minutes {
-            return Err(TimeError(format!(
-                "End time {} (interpreted as {}:{:02}{}) is before start time {} (interpreted as {}:{:02}{}). The range must be within a single day and end time must be after start time.",
-                raw_end, end_h, end_m, end_ampm_str,
-                raw_start, start_h, start_m, start_ampm_str
-            )));
+        format!(
+            "{} \u{2192} {} = {}",
+            range.start.format_24h(),
+            if range.end_of_day {
+                "24:00".to_string()
+            } else {
+                range.end.format_24h()
+            },
+            total
+        )
+    } else if options.print_minutes {
+        format!(
+            "{} minutes",
+            format_minutes(u64::from(span.total_minutes()), options)
+        )
+    } else if options.decimals.is_some() {
+        format!("{} hours", format_hours(span.as_hours_f64(), options, true))
+    } else {
+        span.to_string()
+    }
+}
+
+/// Returns the `--warn-implicit` warning for a range whose AM/PM was assumed, if any.
+fn implicit_warning(range: &RangeCalculation, options: &OutputOptions) -> Option<String> {
+    if !options.warn_implicit || !range.assumed_meridian {
+        return None;
+    }
+    Some(format!(
+        "Warning: AM/PM was not given for every time; interpreted as {}-{}.",
+        range.start, range.end
+    ))
+}
+
+/// Validates a single time for `--check`, returning its normalized 24-hour form, e.g. "21:00".
+fn check_time(time_str: &str) -> Result<String, TimeError> {
+    TimeOfDay::parse(time_str).map(|time| time.format_24h())
+}
+
+/// The error for a wrong number of arguments: the usage text, or only `message` with
+/// `--quiet`. The usage text is always given when no input was given at all.
+fn usage_error(program_name: &str, inputs: &[&str], quiet: bool, message: &str) -> (String, i32) {
+    if quiet && !inputs.is_empty() {
+        let noun = if inputs.len() == 1 {
+            "argument"
+        } else {
+            "arguments"
+        };
+        (
+            format!("Error: {} Got {} {}.", message, inputs.len(), noun),
+            1,
+        )
+    } else {
+        (usage(program_name), 1)
+    }
+}
+
+/// The error for a failed calculation. In JSON mode it is a {"error": ...} object, which
+/// `main` writes to stdout so callers can parse it.
+fn calculation_error(message: &str, options: &OutputOptions) -> (String, i32) {
+    if options.json {
+        (format!("{{\"error\":{}}}", json_string(message)), 1)
+    } else {
+        (format!("Error: {}", message), 1)
+    }
+}
+
+/// How a range is calculated: `calculate_range`, or `calculate_range_allowing_overnight`
+/// with `--overnight`.
+type RangeCalculator = fn(&str) -> Result<RangeCalculation, TimeError>;
+
+/// Calculates each non-blank stdin line as a range, reporting malformed lines
+/// without stopping. Returns the process exit code: 1 if any line failed, else 0.
+fn run_batch(options: &OutputOptions, calculate: RangeCalculator) -> i32 {
+    let mut exit_code = 0;
+    let mut total = DurationAccumulator::new();
+    let mut errors = 0;
+    for (line_number, result) in process_range_lines_with(io::stdin().lock(), calculate) {
+        match result {
+            Ok(range) => {
+                total.add(range.span);
+                if let Some(warning) = implicit_warning(&range, options) {
+                    eprintln!("Line {}: {}", line_number, warning);
+                }
+                println!("{}", format_result(&range, options));
+            }
+            Err(e) => {
+                if options.json {
+                    println!(
+                        "{{\"line\":{},\"error\":{}}}",
+                        line_number,
+                        json_string(&e.to_string())
+                    );
+                } else {
+                    eprintln!("Line {}: Error: {}", line_number, e);
+                }
+                errors += 1;
+                exit_code = 1;
+            }
         }
-        Ok((end_minutes - start_minutes) as f64 / 60.0)
     }
+    eprintln!("{}", batch_summary(&total, errors, options));
+    exit_code
 }
 
-fn main() {
-    use chrono::Local;
-    use std::env;
-    use std::process;
-    use time_difference::calculate;
-    use time_parsing::parse_time_components;
+/// Summarizes a batch after its last line, e.g. "Total: 42.50 hours across 8 ranges, 2 errors".
+/// It is written to stderr so that stdout keeps one result per line.
+fn batch_summary(total: &DurationAccumulator, errors: u64, options: &OutputOptions) -> String {
+    let plural =
+        |count: u64, noun: &str| format!("{} {}{}", count, noun, if count == 1 { "" } else { "s" });
+    let mut summary = format!(
+        "Total: {} hours across {}",
+        format_hours(total.total_hours(), options, true),
+        plural(total.count(), "range")
+    );
+    if errors > 0 {
+        summary.push_str(&format!(", {}", plural(errors, "error")));
+    }
+    summary
+}
 
-    let args: Vec<String> = env::args().collect();
+/// Runs the calculator on `args`, including the program name, returning the text to print
+/// on success, or the error text and the exit code. Error text is usage or "Error: ..." for
+/// stderr, or a JSON error object for stdout with `--json`. Batch mode prints its results
+/// as it reads them, and notes such as `--warn-implicit` warnings go straight to stderr.
+fn run(args: &[String]) -> Result<String, (String, i32)> {
     let program_name = args
-        .get(0)
+        .first()
         .map_or("time_duration_calculator", |s| s.as_str());
 
-    if args.len() != 2 {
-        eprintln!("Calculates the difference in hours between two times in a day.");
-        eprintln!("Usage:");
-        eprintln!(
-            "  1. Time range: {} \"H(H):MM[am/pm]-H(H):MM[am/pm]\"",
-            program_name
-        );
-        eprintln!("     Example: {} \"09:00AM-05:30PM\"", program_name);
-        eprintln!("     Example (implicit AM/PM for range): {} \"9:00-5:30\" (interprets as 9:00AM-5:30PM)", program_name);
-        eprintln!("  2. Single time (start time assumed AM, end time is current system time): {} \"H(H):MM\"", program_name);
-        eprintln!(
-            "     Example: {} \"09:15\" (interprets as 09:15AM - CurrentSystemTime)",
-            program_name
-        );
-        process::exit(1);
+    let mut options = OutputOptions::default();
+    let mut check = false;
+    let mut since = None;
+    let mut quiet = false;
+    let mut csv_header = false;
+    let mut calculate: RangeCalculator = calculate_range;
+    let mut overnight = false;
+    let mut inputs: Vec<&str> = Vec::new();
+    let mut arg_iter = args.iter().skip(1);
+    while let Some(arg) = arg_iter.next() {
+        match arg.as_str() {
+            "--minutes" => options.print_minutes = true,
+            "--json" => options.json = true,
+            "--csv" => options.csv = true,
+            "--csv-header" => {
+                options.csv = true;
+                csv_header = true;
+            }
+            "-v" | "--verbose" => options.verbose = true,
+            "--raw" => options.raw = true,
+            "--iso" => options.iso = true,
+            "--check" => check = true,
+            "--since" => match arg_iter.next() {
+                Some(time) => since = Some(time.as_str()),
+                None => {
+                    return Err((
+                        "Error: --since expects a time, e.g. \"9:00AM\".".to_string(),
+                        1,
+                    ))
+                }
+            },
+            "--quiet" => quiet = true,
+            "--overnight" => {
+                calculate = calculate_range_allowing_overnight;
+                overnight = true;
+            }
+            "--warn-implicit" => options.warn_implicit = true,
+            "--decimal-comma" => options.decimal_comma = true,
+            "--decimals" => {
+                let decimals = parse_decimals(arg_iter.next().map(|s| s.as_str()))
+                    .map_err(|message| (format!("Error: {}", message), 1))?;
+                options.decimals = Some(decimals);
+            }
+            "--format" => match arg_iter.next().map(|s| parse_template(s)) {
+                Some(Ok(template)) => options.template = Some(template),
+                Some(Err(message)) => return Err((format!("Error: {}", message), 1)),
+                None => {
+                    return Err((
+                        "Error: --format expects a template, e.g. \"{start}-{end}: {hours}h\"."
+                            .to_string(),
+                        1,
+                    ))
+                }
+            },
+            _ => inputs.push(arg),
+        }
     }
 
-    let input_str = args[1].trim();
-    let final_result: Result<f64, TimeError>;
+    if check {
+        if inputs.len() != 1 {
+            return Err(usage_error(
+                program_name,
+                &inputs,
+                quiet,
+                "--check expects exactly one time.",
+            ));
+        }
+        return match check_time(inputs[0]) {
+            Ok(time) if options.json => Ok(format!(
+                "{{\"input\":{},\"time\":{}}}",
+                json_string(inputs[0]),
+                json_string(&time)
+            )),
+            Ok(time) => Ok(time),
+            Err(e) => Err(calculation_error(&e.to_string(), &options)),
+        };
+    }
 
-    if input_str.contains('-') {
-        final_result = calculate(input_str);
+    // JSON takes precedence over CSV, so the header is only wanted for CSV rows.
+    let csv_header = csv_header && !options.json;
+    if let Some(time) = since {
+        if !inputs.is_empty() {
+            return Err(usage_error(
+                program_name,
+                &inputs,
+                quiet,
+                "--since takes a time and no range.",
+            ));
+        }
+        if !overnight && prints_only_hours(&options) {
+            let hours =
+                hours_since(time).map_err(|e| calculation_error(&e.to_string(), &options))?;
+            return Ok(if options.raw {
+                format_hours(hours, &options, false)
+            } else {
+                format!("{} hours", format_hours(hours, &options, true))
+            });
+        }
+        let start =
+            TimeOfDay::parse(time).map_err(|e| calculation_error(&e.to_string(), &options))?;
+        let range_str = format!("{}-{}", start, TimeOfDay::now());
+        return run_range(&range_str, calculate, &options, csv_header);
+    }
+    if inputs.is_empty() && !io::stdin().is_terminal() {
+        if csv_header {
+            println!("{}", CSV_HEADER);
+        }
+        return match run_batch(&options, calculate) {
+            0 => Ok(String::new()),
+            exit_code => Err((String::new(), exit_code)),
+        };
+    }
+
+    if !(1..=2).contains(&inputs.len()) {
+        return Err(usage_error(
+            program_name,
+            &inputs,
+            quiet,
+            "Expected a range, or a start and an end time.",
+        ));
+    }
+
+    // Ranges are bounded by the library, but a single time is parsed here first.
+    if let Some(input) = inputs
+        .iter()
+        .find(|input| input.len() > DEFAULT_MAX_INPUT_LENGTH)
+    {
+        let error = TimeError::InputTooLong {
+            length: input.len(),
+            max_length: DEFAULT_MAX_INPUT_LENGTH,
+        };
+        return Err(calculation_error(&error.to_string(), &options));
+    }
+    let input_str = inputs[0].trim();
+    let range_str = if let [start, end] = inputs[..] {
+        format!("{}-{}", start.trim(), end.trim())
+    } else if input_str.contains(['-', '+', '\u{2212}', '\u{2013}', '\u{2014}']) {
+        input_str.to_string()
     } else {
-        let (input_h, input_m, ampm_opt) = match parse_time_components(input_str) {
+        let (input_h, input_m, input_s, ampm_opt) = match parse_time_components(input_str) {
             Ok(components) => components,
+            Err(e) if options.json => return Err(calculation_error(&e.to_string(), &options)),
             Err(e) => {
-                eprintln!("Error parsing input time '{}': {}", input_str, e);
-                process::exit(1);
+                return Err((
+                    format!("Error parsing input time '{}': {}", input_str, e),
+                    1,
+                ))
             }
         };
         if ampm_opt.is_some() {
-            eprintln!("Error: For single time input (e.g., '9:15'), do not specify AM/PM.");
+            let message = "For single time input (e.g., '9:15'), do not specify AM/PM.";
+            if options.json || quiet {
+                return Err(calculation_error(message, &options));
+            }
+            return Err((
+                format!(
+                    "Error: {}\nThe input time is assumed to be AM, and the end time is the current system time.",
+                    message
+                ),
+                1,
+            ));
+        }
+        let start_time_str = if input_s == 0 {
+            format!("{}:{:02}AM", input_h, input_m)
+        } else {
+            format!("{}:{:02}:{:02}AM", input_h, input_m, input_s)
+        };
+        let range_str = format!("{}-{}", start_time_str, TimeOfDay::now());
+        if !quiet {
             eprintln!(
-                "The input time is assumed to be AM, and the end time is the current system time."
+                "Interpreting single time input '{}' as range: {}",
+                input_str, range_str
             );
-            process::exit(1);
         }
-        let start_time_str = format!("{}:{:02}AM", input_h, input_m);
-        let now = Local::now();
-        let current_hour_12 = now.format("%I").to_string();
-        let current_minute = now.format("%M").to_string();
-        let current_ampm = now.format("%p").to_string().to_uppercase();
-        let end_time_str = format!("{}:{}{}", current_hour_12, current_minute, current_ampm);
-        let range_str = format!("{}-{}", start_time_str, end_time_str);
-        eprintln!(
-            "Interpreting single time input '{}' as range: {}",
-            input_str, range_str
-        );
-        final_result = calculate(&range_str);
-    }
-
-    match final_result {
-        Ok(hours) => {
-            let total_minutes = (hours * 60.0).round() as u32;
-            let h = total_minutes / 60;
-            let m = total_minutes % 60;
-            println!("{}h {}m", h, m);
+        range_str
+    };
+
+    run_range(&range_str, calculate, &options, csv_header)
+}
+
+/// Calculates a single range and formats its result, preceded by the CSV header if wanted.
+fn run_range(
+    range_str: &str,
+    calculate: RangeCalculator,
+    options: &OutputOptions,
+    csv_header: bool,
+) -> Result<String, (String, i32)> {
+    match calculate(range_str) {
+        Ok(range) => {
+            if let Some(warning) = implicit_warning(&range, options) {
+                eprintln!("{}", warning);
+            }
+            let result = format_result(&range, options);
+            if csv_header {
+                Ok(format!("{}\n{}", CSV_HEADER, result))
+            } else {
+                Ok(result)
+            }
         }
-        Err(e) => {
-            eprintln!("Error: {}", e);
-            process::exit(1);
+        Err(e) => Err(calculation_error(&e.to_string(), options)),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    match run(&args) {
+        Ok(output) => {
+            // Batch mode has already printed its results.
+            if !output.is_empty() {
+                println!("{}", output);
+            }
+        }
+        Err((message, exit_code)) => {
+            if message.starts_with('{') {
+                println!("{}", message);
+            } else if !message.is_empty() {
+                eprintln!("{}", message);
+            }
+            process::exit(exit_code);
         }
     }
 }
@@ -253,115 +694,410 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_parse_time_components_valid() {
+    fn test_json_string_escapes() {
+        assert_eq!(json_string("9:00AM"), "\"9:00AM\"");
+        assert_eq!(json_string("a\"b\\c\nd"), "\"a\\\"b\\\\c\\nd\"");
+        assert_eq!(json_string("\u{1}"), "\"\\u0001\"");
+    }
+
+    #[test]
+    fn test_format_result_json() {
+        let options = OutputOptions {
+            json: true,
+            ..Default::default()
+        };
         assert_eq!(
-            time_parsing::parse_time_components("09:00AM"),
-            Ok((9, 0, Some("AM".to_string())))
+            format_result(&calculate_range("9:00AM-5:30PM").unwrap(), &options),
+            "{\"start\":\"09:00AM\",\"end\":\"05:30PM\",\"hours\":8.5,\"minutes\":510}"
         );
+    }
+
+    #[test]
+    fn test_format_result_verbose() {
+        let mut options = OutputOptions {
+            verbose: true,
+            ..Default::default()
+        };
+        let range = calculate_range("9:00-5:30").unwrap();
         assert_eq!(
-            time_parsing::parse_time_components("9:00am"),
-            Ok((9, 0, Some("AM".to_string())))
+            format_result(&range, &options),
+            "09:00 \u{2192} 17:30 = 8.50 hours"
         );
+        options.print_minutes = true;
         assert_eq!(
-            time_parsing::parse_time_components("12:30PM"),
-            Ok((12, 30, Some("PM".to_string())))
+            format_result(&range, &options),
+            "09:00 \u{2192} 17:30 = 510 minutes"
         );
+    }
+
+    #[test]
+    fn test_parse_decimals() {
+        assert_eq!(parse_decimals(Some("0")), Ok(0));
+        assert_eq!(parse_decimals(Some("10")), Ok(10));
+        assert!(parse_decimals(Some("11")).is_err());
+        assert!(parse_decimals(Some("-1")).is_err());
+        assert!(parse_decimals(Some("two")).is_err());
+        assert!(parse_decimals(None).is_err());
+    }
+
+    #[test]
+    fn test_format_result_decimals() {
+        let mut options = OutputOptions {
+            decimals: Some(0),
+            ..Default::default()
+        };
+        let range = calculate_range("9:00AM-5:20PM").unwrap();
+        assert_eq!(format_result(&range, &options), "8 hours");
+        options.decimals = Some(4);
+        assert_eq!(format_result(&range, &options), "8.3333 hours");
+        options.verbose = true;
         assert_eq!(
-            time_parsing::parse_time_components("01:15pm"),
-            Ok((1, 15, Some("PM".to_string())))
+            format_result(&range, &options),
+            "09:00 \u{2192} 17:20 = 8.3333 hours"
         );
+    }
+
+    #[test]
+    fn test_format_result_raw() {
+        let mut options = OutputOptions {
+            verbose: true,
+            raw: true,
+            ..Default::default()
+        };
+        let range = calculate_range("9:00AM-5:30PM").unwrap();
+        assert_eq!(format_result(&range, &options), "8.50");
+        options.decimals = Some(0);
+        assert_eq!(format_result(&range, &options), "8");
+        options.print_minutes = true;
+        assert_eq!(format_result(&range, &options), "510");
+    }
+
+    #[test]
+    fn test_check_time() {
+        assert_eq!(check_time("9:00PM"), Ok("21:00".to_string()));
+        assert_eq!(check_time("9:00"), Ok("09:00".to_string()));
+        assert_eq!(check_time("12:00:30am"), Ok("00:00:30".to_string()));
+        assert!(check_time("13:00PM").is_err());
+        assert!(check_time("9:00AM-5:00PM").is_err());
+    }
+
+    #[test]
+    fn test_implicit_warning() {
+        let mut options = OutputOptions::default();
+        let implicit = calculate_range("9:00-5:30").unwrap();
+        let explicit = calculate_range("9:00AM-5:30PM").unwrap();
+        assert_eq!(implicit_warning(&implicit, &options), None);
+        options.warn_implicit = true;
         assert_eq!(
-            time_parsing::parse_time_components("09:00"),
-            Ok((9, 0, None))
+            implicit_warning(&implicit, &options).as_deref(),
+            Some("Warning: AM/PM was not given for every time; interpreted as 09:00AM-05:30PM.")
         );
+        assert_eq!(implicit_warning(&explicit, &options), None);
         assert_eq!(
-            time_parsing::parse_time_components("9:00"),
-            Ok((9, 0, None))
+            format_result(&implicit, &options),
+            format_result(&explicit, &options)
         );
+    }
+
+    #[test]
+    fn test_format_result_iso() {
+        let options = OutputOptions {
+            iso: true,
+            ..Default::default()
+        };
         assert_eq!(
-            time_parsing::parse_time_components("12:00"),
-            Ok((12, 0, None))
+            format_result(&calculate_range("9:00AM-5:30PM").unwrap(), &options),
+            "PT8H30M"
         );
+    }
+
+    #[test]
+    fn test_parse_template() {
         assert_eq!(
-            time_parsing::parse_time_components(" 07:00AM "),
-            Ok((7, 0, Some("AM".to_string())))
+            parse_template("{start}-{end}: {{{hours}}}"),
+            Ok(vec![
+                TemplatePart::Start,
+                TemplatePart::Literal("-".to_string()),
+                TemplatePart::End,
+                TemplatePart::Literal(": {".to_string()),
+                TemplatePart::Hours,
+                TemplatePart::Literal("}".to_string()),
+            ])
         );
+        assert_eq!(parse_template(""), Ok(vec![]));
+        assert!(parse_template("{hour}").unwrap_err().contains("'{hour}'"));
+        assert!(parse_template("{start").is_err());
+        assert!(parse_template("start}").is_err());
+    }
+
+    #[test]
+    fn test_format_result_template() {
+        let mut options = OutputOptions {
+            verbose: true,
+            template: parse_template("{start} to {end} = {hours}h ({minutes}m, {iso})").ok(),
+            ..Default::default()
+        };
+        let range = calculate_range("9:00AM-5:30PM").unwrap();
         assert_eq!(
-            time_parsing::parse_time_components("7:00"),
-            Ok((7, 0, None))
+            format_result(&range, &options),
+            "09:00AM to 05:30PM = 8.50h (510m, PT8H30M)"
+        );
+        options.decimals = Some(1);
+        assert_eq!(
+            format_result(&range, &options),
+            "09:00AM to 05:30PM = 8.5h (510m, PT8H30M)"
         );
     }
 
     #[test]
-    fn test_parse_time_components_invalid_format() {
-        assert!(time_parsing::parse_time_components("900AM").is_err());
-        assert!(time_parsing::parse_time_components("09:00XM").is_err());
-        assert!(time_parsing::parse_time_components("09:00PMM").is_err());
-        assert!(time_parsing::parse_time_components("090:00AM").is_err());
-        assert!(time_parsing::parse_time_components("09:0AM").is_err());
-        assert!(time_parsing::parse_time_components("09:000AM").is_err());
-        assert!(time_parsing::parse_time_components(":00AM").is_err());
-        assert!(time_parsing::parse_time_components("09:AM").is_err());
-        assert!(time_parsing::parse_time_components("9").is_err());
-        assert!(time_parsing::parse_time_components("9AM").is_err());
-        assert!(time_parsing::parse_time_components("AM").is_err());
-        assert!(time_parsing::parse_time_components("").is_err());
-        assert!(time_parsing::parse_time_components("10:30 AM").is_err());
+    fn test_csv_field() {
+        assert_eq!(csv_field("09:00AM"), "09:00AM");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
     }
 
     #[test]
-    fn test_parse_time_components_invalid_values() {
-        assert!(time_parsing::parse_time_components("00:00AM").is_err());
-        assert!(time_parsing::parse_time_components("13:00AM").is_err());
-        assert!(time_parsing::parse_time_components("09:60AM").is_err());
-        assert!(time_parsing::parse_time_components("AA:00AM").is_err());
-        assert!(time_parsing::parse_time_components("09:BBAM").is_err());
+    fn test_format_result_csv() {
+        let mut options = OutputOptions {
+            print_minutes: true,
+            csv: true,
+            verbose: true,
+            ..Default::default()
+        };
+        let range = calculate_range("9:00AM-5:30PM").unwrap();
+        assert_eq!(format_result(&range, &options), "09:00AM,05:30PM,8.50,510");
+        options.decimals = Some(1);
+        assert_eq!(format_result(&range, &options), "09:00AM,05:30PM,8.5,510");
+        options.json = true;
+        assert!(format_result(&range, &options).starts_with('{'));
+    }
+
+    fn run_args(args: &[&str]) -> Result<String, (String, i32)> {
+        let args: Vec<String> = std::iter::once("time_duration_calculator")
+            .chain(args.iter().copied())
+            .map(String::from)
+            .collect();
+        run(&args)
     }
 
     #[test]
-    fn test_convert_components_to_minutes_valid() {
+    fn test_run_ranges() {
+        assert_eq!(run_args(&["9:00AM-5:30PM"]), Ok("8h 30m".to_string()));
+        assert_eq!(run_args(&["9:00AM", "5:30PM"]), Ok("8h 30m".to_string()));
+        assert_eq!(run_args(&["9:00AM+8h"]), Ok("8h 0m".to_string()));
         assert_eq!(
-            time_conversion::to_minutes(9, 0, "AM", "9:00AM"),
-            Ok(9 * 60)
+            run_args(&["--minutes", "--raw", "9:00-5:30"]),
+            Ok("510".to_string())
         );
         assert_eq!(
-            time_conversion::to_minutes(12, 0, "AM", "12:00AM"),
-            Ok(0 * 60)
+            run_args(&["--csv-header", "9:00-5:30"]),
+            Ok("start,end,hours,minutes\n09:00AM,05:30PM,8.50,510".to_string())
         );
         assert_eq!(
-            time_conversion::to_minutes(5, 30, "PM", "05:30PM"),
-            Ok(17 * 60 + 30)
+            run_args(&["--overnight", "--decimals", "1", "10:00PM-2:00AM"]),
+            Ok("4.0 hours".to_string())
         );
+        assert_eq!(run_args(&["--check", "9:00PM"]), Ok("21:00".to_string()));
+    }
+
+    #[test]
+    fn test_run_errors() {
+        let (message, exit_code) = run_args(&["5:00PM-9:00AM"]).unwrap_err();
+        assert!(message.starts_with("Error: "), "{}", message);
+        assert_eq!(exit_code, 1);
+
+        let (message, _) = run_args(&["--json", "5:00PM-9:00AM"]).unwrap_err();
+        assert!(message.starts_with("{\"error\":"), "{}", message);
+
+        let (message, _) = run_args(&["a", "b", "c"]).unwrap_err();
+        assert!(message.starts_with("Calculates the difference"));
         assert_eq!(
-            time_conversion::to_minutes(12, 0, "PM", "12:00PM"),
-            Ok(12 * 60)
+            run_args(&["--quiet", "a", "b", "c"]),
+            Err((
+                "Error: Expected a range, or a start and an end time. Got 3 arguments.".to_string(),
+                1
+            ))
+        );
+        assert_eq!(
+            run_args(&["--quiet", "9:15AM"]),
+            Err((
+                "Error: For single time input (e.g., '9:15'), do not specify AM/PM.".to_string(),
+                1
+            ))
         );
+        assert!(run_args(&["--decimals", "11", "9:00-5:00"]).is_err());
+        assert!(run_args(&["--format"]).is_err());
+        assert!(run_args(&["--check", "13:00PM"]).is_err());
     }
 
     #[test]
-    fn test_calculate_difference_explicit_ampm() {
-        assert_eq!(time_difference::calculate("09:00AM-05:30PM"), Ok(8.5));
-        assert_eq!(time_difference::calculate("9:00AM-5:30PM"), Ok(8.5));
+    fn test_decimal_comma() {
+        assert_eq!(
+            run_args(&["--decimal-comma", "--decimals", "2", "9:00-5:30"]),
+            Ok("8,50 hours".to_string())
+        );
+        assert_eq!(
+            run_args(&["--decimal-comma", "--raw", "9:00-5:30"]),
+            Ok("8,50".to_string())
+        );
+        assert_eq!(
+            run_args(&["--decimal-comma", "--format", "{hours}h.", "9:00-5:30"]),
+            Ok("8,50h.".to_string())
+        );
+        assert_eq!(
+            run_args(&["--decimal-comma", "--csv", "9:00-5:30"]),
+            Ok("09:00AM,05:30PM,8.50,510".to_string())
+        );
+        assert_eq!(
+            run_args(&["--decimal-comma", "--json", "9:00-5:30"]),
+            Ok(
+                "{\"start\":\"09:00AM\",\"end\":\"05:30PM\",\"hours\":8.5,\"minutes\":510}"
+                    .to_string()
+            )
+        );
+    }
+
+    #[test]
+    fn test_run_since() {
+        assert!(run_args(&["--since", "12:00AM", "--raw"]).is_ok());
+        assert!(run_args(&["--since", "12:00AM", "--decimals", "1"])
+            .unwrap()
+            .ends_with(" hours"));
+        assert!(run_args(&["--since", "12:00AM", "--json"])
+            .unwrap()
+            .starts_with("{\"start\":\"12:00AM\","));
+        assert!(run_args(&["--since", "11:59:59PM", "--raw", "--overnight"]).is_ok());
+        assert!(run_args(&["--since", "13:00PM"]).is_err());
+        assert!(run_args(&["--since"]).is_err());
+        let (message, _) = run_args(&["--quiet", "--since", "9:00AM", "5:00PM"]).unwrap_err();
+        assert_eq!(
+            message,
+            "Error: --since takes a time and no range. Got 1 argument."
+        );
     }
 
     #[test]
-    fn test_calculate_difference_implicit_ampm_range() {
-        assert_eq!(time_difference::calculate("09:00-05:30"), Ok(8.5));
-        assert_eq!(time_difference::calculate("9:00-5:30"), Ok(8.5));
+    fn test_batch_summary() {
+        let mut options = OutputOptions::default();
+        let mut total = DurationAccumulator::new();
+        assert_eq!(
+            batch_summary(&total, 0, &options),
+            "Total: 0.00 hours across 0 ranges"
+        );
+        total.add(calculate_range("9:00AM-5:30PM").unwrap().span);
+        assert_eq!(
+            batch_summary(&total, 1, &options),
+            "Total: 8.50 hours across 1 range, 1 error"
+        );
+        total.add(calculate_range("9:00AM-11:00AM").unwrap().span);
+        options.decimal_comma = true;
+        assert_eq!(
+            batch_summary(&total, 2, &options),
+            "Total: 10,50 hours across 2 ranges, 2 errors"
+        );
     }
 
     #[test]
-    fn test_calculate_difference_mixed_ampm_error() {
-        assert!(time_difference::calculate("09:00AM-05:00").is_err());
-        assert!(time_difference::calculate("09:00-05:00PM").is_err());
+    fn test_localize_number() {
+        let mut options = OutputOptions::default();
+        assert_eq!(localize_number("123456", &options, true), "123,456");
+        assert_eq!(
+            localize_number("1234567.50", &options, true),
+            "1,234,567.50"
+        );
+        assert_eq!(localize_number("999.99", &options, true), "999.99");
+        assert_eq!(localize_number("123456", &options, false), "123456");
+        assert_eq!(format_minutes(1439, &options), "1,439");
+        options.decimal_comma = true;
+        assert_eq!(localize_number("1234.50", &options, true), "1.234,50");
+        assert_eq!(localize_number("1234.50", &options, false), "1234,50");
+        assert_eq!(format_hours(1234.5, &options, true), "1.234,50");
     }
 
     #[test]
-    fn test_calculate_difference_end_before_start_error() {
-        let result = time_difference::calculate("05:00PM-09:00AM");
-        assert!(result.is_err());
-        if let Err(TimeError(msg)) = result {
-            assert!(msg.contains("End time 09:00AM (interpreted as 9:00AM) is before start time 05:00PM (interpreted as 5:00PM)"));
-        }
+    fn test_run_groups_thousands() {
+        assert_eq!(
+            run_args(&["--minutes", "12:00AM-11:59PM"]),
+            Ok("1,439 minutes".to_string())
+        );
+        assert_eq!(
+            run_args(&["--minutes", "--raw", "12:00AM-11:59PM"]),
+            Ok("1439".to_string())
+        );
+    }
+
+    #[test]
+    fn test_run_unicode_dash() {
+        assert_eq!(
+            run_args(&["--raw", "9:00AM\u{2013}5:30PM"]),
+            Ok("8.50".to_string())
+        );
+    }
+
+    #[test]
+    fn test_prints_only_hours() {
+        let mut options = OutputOptions {
+            raw: true,
+            ..Default::default()
+        };
+        assert!(prints_only_hours(&options));
+        options.print_minutes = true;
+        assert!(!prints_only_hours(&options));
+        let mut options = OutputOptions {
+            decimals: Some(2),
+            ..Default::default()
+        };
+        assert!(prints_only_hours(&options));
+        options.verbose = true;
+        assert!(!prints_only_hours(&options));
+        assert!(!prints_only_hours(&OutputOptions::default()));
+        assert!(!prints_only_hours(&OutputOptions {
+            raw: true,
+            json: true,
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn test_run_end_of_day() {
+        assert_eq!(run_args(&["22:00-24:00"]), Ok("2h 0m".to_string()));
+        assert_eq!(run_args(&["00:00-24:00"]), Ok("24h 0m".to_string()));
+        assert_eq!(
+            run_args(&["--overnight", "22:00-24:00"]),
+            Ok("2h 0m".to_string())
+        );
+        assert_eq!(
+            run_args(&["--json", "22:00-24:00"]),
+            Ok("{\"start\":\"10:00PM\",\"end\":\"24:00\",\"hours\":2,\"minutes\":120}".to_string())
+        );
+        assert_eq!(
+            run_args(&["-v", "22:00", "24:00"]),
+            Ok("22:00 \u{2192} 24:00 = 2.00 hours".to_string())
+        );
+        assert_eq!(
+            run_args(&["--format", "{start}-{end}", "22:00-24:00"]),
+            Ok("10:00PM-24:00".to_string())
+        );
+    }
+
+    #[test]
+    fn test_run_input_too_long() {
+        let long_range = format!("9:00AM-{}", "5".repeat(100));
+        let long_time = "9".repeat(100);
+        let expected = "Error: Input is 107 bytes long, which exceeds the maximum of 64 bytes.";
+        assert_eq!(run_args(&[&long_range]), Err((expected.to_string(), 1)));
+        let (message, exit_code) = run_args(&[&long_time]).unwrap_err();
+        assert!(
+            message.contains("exceeds the maximum of 64 bytes"),
+            "{}",
+            message
+        );
+        assert_eq!(exit_code, 1);
+        let (message, _) = run_args(&["9:00AM", &long_time]).unwrap_err();
+        assert!(
+            message.contains("exceeds the maximum of 64 bytes"),
+            "{}",
+            message
+        );
     }
 }
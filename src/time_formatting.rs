@@ -0,0 +1,76 @@
+use crate::time_span::TimeSpan;
+use alloc::format;
+use alloc::string::String;
+
+/// Renders a duration in hours as "HH:MM", rounded to the nearest minute.
+/// Durations of 24 hours or more do not wrap (25.5 gives "25:30"), and negative
+/// durations are prefixed with '-'.
+pub fn format_hours_hhmm(hours: f64) -> String {
+    // Rounded by hand because f64::abs and f64::round need std.
+    let magnitude = if hours < 0.0 { -hours } else { hours };
+    let total_minutes = (magnitude * 60.0 + 0.5) as u64;
+    let sign = if hours < 0.0 && total_minutes > 0 {
+        "-"
+    } else {
+        ""
+    };
+    format!(
+        "{}{:02}:{:02}",
+        sign,
+        total_minutes / 60,
+        total_minutes % 60
+    )
+}
+
+/// Renders a span as an ISO-8601 duration, e.g. "PT8H30M" for 8.5 hours or "PT23H59M"
+/// for 1439 minutes. Zero components are left out, and an empty span is "PT0S".
+pub fn format_iso8601(span: TimeSpan) -> String {
+    if span.total_seconds() == 0 {
+        return String::from("PT0S");
+    }
+    let mut iso = String::from("PT");
+    for (value, unit) in [
+        (span.total_seconds() / 3600, 'H'),
+        (span.minutes(), 'M'),
+        (span.seconds(), 'S'),
+    ] {
+        if value != 0 {
+            iso.push_str(&format!("{}{}", value, unit));
+        }
+    }
+    iso
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_hours_hhmm() {
+        assert_eq!(format_hours_hhmm(8.5), "08:30");
+        assert_eq!(format_hours_hhmm(1439.0 / 60.0), "23:59");
+        assert_eq!(format_hours_hhmm(25.5), "25:30");
+        assert_eq!(format_hours_hhmm(0.0), "00:00");
+        assert_eq!(format_hours_hhmm(100.0), "100:00");
+        assert_eq!(format_hours_hhmm(-8.0), "-08:00");
+        assert_eq!(format_hours_hhmm(-0.001), "00:00");
+    }
+
+    #[test]
+    fn test_format_hours_hhmm_rounds_to_nearest_minute() {
+        assert_eq!(format_hours_hhmm(7.0 / 60.0), "00:07");
+        assert_eq!(format_hours_hhmm(0.999), "01:00");
+        assert_eq!(format_hours_hhmm(30.0 / 3600.0), "00:01");
+        assert_eq!(format_hours_hhmm(29.0 / 3600.0), "00:00");
+    }
+
+    #[test]
+    fn test_format_iso8601() {
+        assert_eq!(format_iso8601(TimeSpan::from_minutes(510)), "PT8H30M");
+        assert_eq!(format_iso8601(TimeSpan::from_minutes(1439)), "PT23H59M");
+        assert_eq!(format_iso8601(TimeSpan::from_minutes(480)), "PT8H");
+        assert_eq!(format_iso8601(TimeSpan::from_minutes(45)), "PT45M");
+        assert_eq!(format_iso8601(TimeSpan::from_seconds(3605)), "PT1H5S");
+        assert_eq!(format_iso8601(TimeSpan::from_minutes(0)), "PT0S");
+    }
+}
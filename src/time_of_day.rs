@@ -0,0 +1,296 @@
+use crate::time_conversion::to_seconds;
+use crate::time_conversion::Meridian;
+use crate::time_parsing::{parse_twelve_hour_fields, ParsedTime};
+use crate::time_span::TimeSpan;
+use crate::TimeError;
+use alloc::format;
+use alloc::string::{String, ToString};
+
+pub(crate) const SECONDS_PER_DAY: u32 = 24 * 60 * 60;
+
+/// A time of day, stored as seconds from midnight so that times compare chronologically
+/// and hash the same however they were written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimeOfDay {
+    seconds_from_midnight: u32,
+}
+
+impl TimeOfDay {
+    /// 12:00AM, the start of the day.
+    pub const MIDNIGHT: TimeOfDay = TimeOfDay {
+        seconds_from_midnight: 0,
+    };
+
+    /// Parses a 12-hour time string such as "9:00AM" or "10:30:15PM".
+    /// A time without AM/PM is read as AM, matching the CLI's single-time rule.
+    pub fn parse(time_str: &str) -> Result<TimeOfDay, TimeError> {
+        let parsed = parse_twelve_hour_fields(time_str, false)?;
+        TimeOfDay::from_parsed(parsed, parsed.meridian.unwrap_or(Meridian::Am), time_str)
+    }
+
+    /// Builds a time from a parsed 12-hour time, using `meridian` in place of any it has.
+    pub(crate) fn from_parsed(
+        parsed: ParsedTime,
+        meridian: Meridian,
+        original_time_str: &str,
+    ) -> Result<TimeOfDay, TimeError> {
+        TimeOfDay::from_components(
+            parsed.hour12,
+            parsed.minute,
+            parsed.second.unwrap_or(0),
+            meridian.as_str(),
+            original_time_str,
+        )
+    }
+
+    /// Builds a time from 12-hour components as returned by `parse_time_components`.
+    pub fn from_components(
+        hour12: u32,
+        minute: u32,
+        second: u32,
+        ampm_indicator: &str,
+        original_time_str: &str,
+    ) -> Result<TimeOfDay, TimeError> {
+        let seconds_from_midnight =
+            to_seconds(hour12, minute, second, ampm_indicator, original_time_str)?;
+        Ok(TimeOfDay {
+            seconds_from_midnight,
+        })
+    }
+
+    /// Builds a time from a 24-hour hour, minute and second, or `None` if any is out of range.
+    pub fn from_hms(hour: u32, minute: u32, second: u32) -> Option<TimeOfDay> {
+        if hour > 23 || minute > 59 || second > 59 {
+            return None;
+        }
+        Some(TimeOfDay {
+            seconds_from_midnight: (hour * 60 + minute) * 60 + second,
+        })
+    }
+
+    /// Returns the hour in 24-hour format (0-23).
+    pub fn hour(&self) -> u32 {
+        self.seconds_from_midnight / 3600
+    }
+
+    /// Returns the hour in 12-hour format (1-12).
+    pub fn hour12(&self) -> u32 {
+        match self.hour() % 12 {
+            0 => 12,
+            hour12 => hour12,
+        }
+    }
+
+    /// Returns "AM" before noon and "PM" from noon onwards.
+    pub fn meridian(&self) -> &'static str {
+        if self.hour() < 12 {
+            "AM"
+        } else {
+            "PM"
+        }
+    }
+
+    /// Returns the minute within the hour.
+    pub fn minute(&self) -> u32 {
+        self.seconds_from_midnight / 60 % 60
+    }
+
+    /// Returns the second within the minute.
+    pub fn second(&self) -> u32 {
+        self.seconds_from_midnight % 60
+    }
+
+    /// Returns the whole minutes elapsed since midnight.
+    pub fn minutes_from_midnight(&self) -> u32 {
+        self.seconds_from_midnight / 60
+    }
+
+    /// Returns the seconds elapsed since midnight.
+    pub fn seconds_from_midnight(&self) -> u32 {
+        self.seconds_from_midnight
+    }
+
+    /// Returns the time `span` later, or `None` if that is past the end of the day.
+    pub fn checked_add(&self, span: TimeSpan) -> Option<TimeOfDay> {
+        let seconds_from_midnight = self
+            .seconds_from_midnight
+            .checked_add(span.total_seconds())?;
+        (seconds_from_midnight < SECONDS_PER_DAY).then_some(TimeOfDay {
+            seconds_from_midnight,
+        })
+    }
+
+    /// Returns the time `span` later, wrapping past midnight into the next day.
+    pub fn wrapping_add(&self, span: TimeSpan) -> TimeOfDay {
+        TimeOfDay {
+            seconds_from_midnight: ((u64::from(self.seconds_from_midnight)
+                + u64::from(span.total_seconds()))
+                % u64::from(SECONDS_PER_DAY)) as u32,
+        }
+    }
+
+    /// Formats the time in 24-hour form, e.g. "17:30", with ":SS" only when seconds are non-zero.
+    pub fn format_24h(&self) -> String {
+        format!(
+            "{:02}:{:02}{}",
+            self.hour(),
+            self.minute(),
+            seconds_suffix(self.second())
+        )
+    }
+}
+
+/// Returns the time `minutes` after `start_str`, in canonical form, e.g. "9:00AM" plus 510
+/// gives "05:30PM". A start without AM/PM is read as AM. An end past 11:59PM is an error.
+pub fn add_duration(start_str: &str, minutes: u32) -> Result<String, TimeError> {
+    let start = TimeOfDay::parse(start_str)?;
+    start
+        .checked_add(TimeSpan::from_seconds(minutes.saturating_mul(60)))
+        .map(|end| end.to_string())
+        .ok_or_else(|| TimeError::PastMidnight {
+            start: start_str.to_string(),
+            minutes,
+        })
+}
+
+/// Like `add_duration`, but an end past 11:59PM wraps into the next day, so "10:00PM"
+/// plus 240 gives "02:00AM".
+pub fn add_duration_allowing_overnight(start_str: &str, minutes: u32) -> Result<String, TimeError> {
+    let start = TimeOfDay::parse(start_str)?;
+    // Whole days do not change the time of day, and dropping them keeps the seconds in range.
+    let minutes = minutes % (SECONDS_PER_DAY / 60);
+    Ok(start
+        .wrapping_add(TimeSpan::from_minutes(minutes))
+        .to_string())
+}
+
+/// Renders ":SS" for a non-zero second so formatted times only show seconds when they matter.
+pub(crate) fn seconds_suffix(second: u32) -> String {
+    if second == 0 {
+        String::new()
+    } else {
+        format!(":{:02}", second)
+    }
+}
+
+/// Formats the time in canonical 12-hour form, e.g. "09:00AM" or "05:30:15PM".
+impl core::fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{:02}:{:02}{}{}",
+            self.hour12(),
+            self.minute(),
+            seconds_suffix(self.second()),
+            self.meridian()
+        )
+    }
+}
+
+impl core::str::FromStr for TimeOfDay {
+    type Err = TimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TimeOfDay::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_of_day_parse() {
+        let time = TimeOfDay::parse("5:30PM").unwrap();
+        assert_eq!(time.hour(), 17);
+        assert_eq!(time.hour12(), 5);
+        assert_eq!(time.minute(), 30);
+        assert_eq!(time.meridian(), "PM");
+        assert_eq!(time.minutes_from_midnight(), 1050);
+        assert_eq!(
+            TimeOfDay::parse("12:00AM").unwrap().minutes_from_midnight(),
+            0
+        );
+        assert_eq!(
+            TimeOfDay::parse("12:00PM").unwrap().minutes_from_midnight(),
+            720
+        );
+        assert_eq!(TimeOfDay::parse("9:15"), TimeOfDay::parse("9:15AM"));
+        assert_eq!("9:15AM".parse::<TimeOfDay>(), TimeOfDay::parse("9:15AM"));
+        assert!(TimeOfDay::parse("13:00PM").is_err());
+    }
+
+    #[test]
+    fn test_time_of_day_ordering() {
+        let morning = TimeOfDay::parse("9:00AM").unwrap();
+        let evening = TimeOfDay::parse("5:30PM").unwrap();
+        let midnight = TimeOfDay::parse("12:00AM").unwrap();
+        assert!(morning < evening);
+        assert!(midnight < morning);
+        assert_eq!(morning.max(evening), evening);
+    }
+
+    #[test]
+    fn test_time_of_day_display() {
+        assert_eq!(TimeOfDay::parse("9:00AM").unwrap().to_string(), "09:00AM");
+        assert_eq!(TimeOfDay::parse("12:05am").unwrap().to_string(), "12:05AM");
+        assert_eq!(
+            TimeOfDay::parse("5:30:15PM").unwrap().to_string(),
+            "05:30:15PM"
+        );
+        assert_eq!(TimeOfDay::parse("5:30PM").unwrap().format_24h(), "17:30");
+        assert_eq!(
+            TimeOfDay::from_hms(0, 0, 5).unwrap().format_24h(),
+            "00:00:05"
+        );
+    }
+
+    #[test]
+    fn test_time_of_day_from_hms() {
+        assert_eq!(
+            TimeOfDay::from_hms(17, 30, 0),
+            TimeOfDay::parse("5:30PM").ok()
+        );
+        assert_eq!(TimeOfDay::from_hms(24, 0, 0), None);
+        assert_eq!(TimeOfDay::from_hms(23, 60, 0), None);
+        assert_eq!(TimeOfDay::from_hms(23, 59, 60), None);
+    }
+
+    #[test]
+    fn test_time_of_day_add() {
+        let time = TimeOfDay::parse("10:00PM").unwrap();
+        assert_eq!(
+            time.checked_add(TimeSpan::from_minutes(119)),
+            TimeOfDay::from_hms(23, 59, 0)
+        );
+        assert_eq!(time.checked_add(TimeSpan::from_minutes(120)), None);
+        assert_eq!(
+            time.wrapping_add(TimeSpan::from_minutes(240)),
+            TimeOfDay::from_hms(2, 0, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_add_duration() {
+        assert_eq!(add_duration("9:00AM", 510), Ok("05:30PM".to_string()));
+        assert_eq!(add_duration("9:00", 0), Ok("09:00AM".to_string()));
+        assert_eq!(add_duration("11:00PM", 59), Ok("11:59PM".to_string()));
+        assert_eq!(
+            add_duration("11:00PM", 60),
+            Err(TimeError::PastMidnight {
+                start: "11:00PM".to_string(),
+                minutes: 60,
+            })
+        );
+        assert!(add_duration("9:00AM", u32::MAX).is_err());
+        assert!(add_duration("13:00PM", 10).is_err());
+        assert_eq!(
+            add_duration_allowing_overnight("10:00PM", 240),
+            Ok("02:00AM".to_string())
+        );
+        assert_eq!(
+            add_duration_allowing_overnight("9:00AM", 510 + 2 * 1440),
+            Ok("05:30PM".to_string())
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use crate::duration_calculator::DEFAULT_MAX_INPUT_LENGTH;
+use crate::time_conversion::ImplicitMeridians;
+use crate::time_difference::{check_input_length, resolve_sides};
+use crate::{FormatIssue, TimeError};
+use alloc::string::ToString;
+
+/// The largest UTC offset accepted, in minutes, matching the real range of ±14:00.
+const MAX_OFFSET_MINUTES: i64 = 14 * 60;
+
+/// Calculates the elapsed hours between two times that may each carry a UTC offset, e.g.
+/// "9:00AM+05:30-5:30PM+00:00" (9:00AM IST to 5:30PM UTC) gives 14.0. Both times are
+/// converted to UTC before differencing, so the end must not be before the start in UTC.
+///
+/// An offset is written `+HH:MM` or `-HH:MM` directly after a time, with two-digit hours.
+/// A time without an offset is taken to be in the same zone as the other time, so a range
+/// with no offsets behaves like `calculate_time_difference_from_range_str`.
+pub fn calculate_with_offsets(range_str: &str) -> Result<f64, TimeError> {
+    // Each '-' is tried as the separator, so bound the input before trying them.
+    check_input_length(range_str, DEFAULT_MAX_INPUT_LENGTH)?;
+    let mut first_error = None;
+    // A '-' may start an offset as well as separate the times, so try each in turn.
+    for (index, _) in range_str.match_indices('-') {
+        match calculate_split(range_str, index) {
+            Ok(hours) => return Ok(hours),
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    Err(first_error.unwrap_or_else(|| TimeError::InvalidFormat {
+        input: range_str.to_string(),
+        issue: FormatIssue::RangeSeparator {
+            separator: "-".to_string(),
+        },
+        position: None,
+    }))
+}
+
+/// Calculates `range_str` taking the '-' at `index` as the range separator.
+fn calculate_split(range_str: &str, index: usize) -> Result<f64, TimeError> {
+    let (start, end) = (range_str[..index].trim(), range_str[index + 1..].trim());
+    if start.is_empty() || end.is_empty() {
+        return Err(TimeError::InvalidFormat {
+            input: range_str.to_string(),
+            issue: FormatIssue::EmptyRangeSide {
+                separator: "-".to_string(),
+            },
+            position: None,
+        });
+    }
+    let (raw_start, start_offset) = split_offset(start, range_str)?;
+    let (raw_end, end_offset) = split_offset(end, range_str)?;
+    let (start_offset, end_offset) = match (start_offset, end_offset) {
+        (Some(start), Some(end)) => (start, end),
+        (Some(offset), None) | (None, Some(offset)) => (offset, offset),
+        (None, None) => (0, 0),
+    };
+    let range = resolve_sides(
+        range_str,
+        raw_start,
+        raw_end,
+        ImplicitMeridians::default(),
+        false,
+    )?;
+    let start_utc = i64::from(range.start.seconds_from_midnight()) - start_offset * 60;
+    let end_utc = i64::from(range.end_seconds()) - end_offset * 60;
+    if end_utc < start_utc {
+        return Err(range.end_before_start_error());
+    }
+    Ok((end_utc - start_utc) as f64 / 3600.0)
+}
+
+/// Splits a trailing `±HH:MM` offset off `side`, returning the time and the offset in minutes.
+fn split_offset<'a>(side: &'a str, range_str: &str) -> Result<(&'a str, Option<i64>), TimeError> {
+    let Some(sign_index) = side.rfind(['+', '-']) else {
+        return Ok((side, None));
+    };
+    let (time, offset) = side.split_at(sign_index);
+    let invalid = || TimeError::InvalidOffset {
+        input: range_str.to_string(),
+        offset: offset.to_string(),
+    };
+    let bytes = offset.as_bytes();
+    let is_hh_mm = bytes.len() == 6
+        && bytes[3] == b':'
+        && [1, 2, 4, 5].iter().all(|&i| bytes[i].is_ascii_digit());
+    if !is_hh_mm {
+        return Err(invalid());
+    }
+    let hours: i64 = offset[1..3].parse().map_err(|_| invalid())?;
+    let minutes: i64 = offset[4..].parse().map_err(|_| invalid())?;
+    let total = hours * 60 + minutes;
+    if minutes > 59 || total > MAX_OFFSET_MINUTES {
+        return Err(invalid());
+    }
+    let total = if offset.starts_with('-') {
+        -total
+    } else {
+        total
+    };
+    Ok((time.trim_end(), Some(total)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_difference::calculate;
+
+    #[test]
+    fn test_calculate_with_offsets() {
+        assert_eq!(
+            calculate_with_offsets("9:00AM+05:30-5:30PM+00:00"),
+            Ok(14.0)
+        );
+        assert_eq!(calculate_with_offsets("9:00AM-05:00-5:30PM-05:00"), Ok(8.5));
+        assert_eq!(calculate_with_offsets("9:00AM+02:00-9:00AM+00:00"), Ok(2.0));
+        assert_eq!(calculate_with_offsets("9:00-05:00-5:30"), Ok(8.5));
+        assert_eq!(
+            calculate_with_offsets("9:00AM+14:00-9:00AM-12:00"),
+            Ok(26.0)
+        );
+    }
+
+    #[test]
+    fn test_calculate_with_offsets_without_offsets() {
+        for range in ["9:00AM-5:30PM", "9:00-5:30", "13:00-17:30"] {
+            assert_eq!(calculate_with_offsets(range), calculate(range));
+        }
+        assert!(matches!(
+            calculate_with_offsets("5:30PM-9:00AM"),
+            Err(TimeError::EndBeforeStart { .. })
+        ));
+        assert!(calculate_with_offsets("9:00AM").is_err());
+    }
+
+    #[test]
+    fn test_calculate_with_offsets_invalid_offsets() {
+        for (range, offset) in [
+            ("9:00AM+14:30-5:00PM+00:00", "+14:30"),
+            ("9:00AM+05:60-5:00PM+00:00", "+05:60"),
+            ("9:00AM+5:30-5:00PM+00:00", "+5:30"),
+        ] {
+            assert_eq!(
+                calculate_with_offsets(range),
+                Err(TimeError::InvalidOffset {
+                    input: range.to_string(),
+                    offset: offset.to_string(),
+                })
+            );
+        }
+        assert_eq!(calculate_with_offsets("9:00AM+00:00-5:00PM+00:00"), Ok(8.0));
+        assert!(matches!(
+            calculate_with_offsets("5:00PM+00:00-9:00AM+00:00"),
+            Err(TimeError::EndBeforeStart { .. })
+        ));
+    }
+}
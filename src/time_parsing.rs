@@ -0,0 +1,1180 @@
+use crate::error::offset_in;
+use crate::time_conversion::Meridian;
+use crate::{FieldIssue, FormatIssue, TimeError};
+use alloc::string::{String, ToString};
+use core::ops::RangeInclusive;
+
+/// The clock a time string is read against, which determines the valid hours.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClockFormat {
+    /// Hours 1-12, optionally followed by AM/PM.
+    TwelveHour,
+    /// Hours 0-23 with no AM/PM.
+    TwentyFourHour,
+}
+
+impl ClockFormat {
+    /// Returns the hours accepted by this clock format.
+    pub fn hour_range(&self) -> RangeInclusive<u32> {
+        match self {
+            ClockFormat::TwelveHour => 1..=12,
+            ClockFormat::TwentyFourHour => 0..=23,
+        }
+    }
+}
+
+impl core::fmt::Display for ClockFormat {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ClockFormat::TwelveHour => write!(f, "12-hour"),
+            ClockFormat::TwentyFourHour => write!(f, "24-hour"),
+        }
+    }
+}
+
+/// Returns the 12-hour components of the keywords "noon" (12:00PM) and "midnight" (12:00AM),
+/// matched case-insensitively as the whole trimmed string.
+pub(crate) fn time_keyword(time_str: &str) -> Option<(u32, u32, u32, Meridian)> {
+    let trimmed = time_str.trim();
+    if trimmed.eq_ignore_ascii_case("noon") {
+        Some((12, 0, 0, Meridian::Pm))
+    } else if trimmed.eq_ignore_ascii_case("midnight") {
+        Some((12, 0, 0, Meridian::Am))
+    } else {
+        None
+    }
+}
+
+/// Splits a dotted meridian such as "a.m.", "P.M." or "p.m" off the end of `time_part`,
+/// returning the time before it (without one separating space) and the meridian.
+fn strip_dotted_meridian(time_part: &str) -> Option<(&str, Meridian)> {
+    let without_dot = time_part.strip_suffix('.').unwrap_or(time_part);
+    let len = without_dot.len();
+    if len < 3 || !without_dot.is_char_boundary(len - 3) {
+        return None;
+    }
+    let (rest, suffix) = without_dot.split_at(len - 3);
+    let meridian = if suffix.eq_ignore_ascii_case("a.m") {
+        Meridian::Am
+    } else if suffix.eq_ignore_ascii_case("p.m") {
+        Meridian::Pm
+    } else {
+        return None;
+    };
+    Some((rest.strip_suffix(' ').unwrap_or(rest), meridian))
+}
+
+/// Parses a time string (e.g., "9:00AM", "09:00", "10:30PM", "9:00:30AM") into its components.
+/// The keywords "noon" and "midnight" parse as 12:00PM and 12:00AM.
+/// Dotted indicators such as "9:00 a.m." or "5:30p.m" are accepted, with or without a
+/// single space, and are returned as "AM" or "PM".
+///
+/// A trailing "AM" or "PM", in any case, is a meridian only when a digit directly precedes it,
+/// as in "9:00AM" or "9:00:30pm". Otherwise it is a format error: "9:00 AM" has a space
+/// before it, "AM" and "PM" alone are missing the time, and "9:00XAM" or "FOOAM:PM" have
+/// something other than a digit before it. The single letters "a" and "p", in any case, are
+/// also accepted directly after a digit, so "9:00a" and "5:30P" read as 9:00AM and 5:30PM.
+/// Returns (hour, minute, second, Option<AM/PM>); second is 0 when not given.
+pub fn parse_time_components(time_str: &str) -> Result<(u32, u32, u32, Option<String>), TimeError> {
+    parse_twelve_hour(time_str, false)
+}
+
+/// The 12-hour parser behind `parse_time_components` and `parse_time_lenient`;
+/// `single_digit_minute` also accepts minutes written as one digit, e.g. "9:5".
+fn parse_twelve_hour(
+    time_str: &str,
+    single_digit_minute: bool,
+) -> Result<(u32, u32, u32, Option<String>), TimeError> {
+    parse_twelve_hour_fields(time_str, single_digit_minute).map(ParsedTime::into_components)
+}
+
+/// The pieces of a 12-hour time as written, before any AM/PM is assumed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParsedTime {
+    /// The hour, 1-12.
+    pub hour12: u32,
+    /// The minute, 0-59.
+    pub minute: u32,
+    /// The second, or `None` if the time has no seconds field, as in "9:00AM".
+    pub second: Option<u32>,
+    /// The AM/PM given with the time, or `None` if it has none, as in "9:00".
+    pub meridian: Option<Meridian>,
+}
+
+impl ParsedTime {
+    /// Converts to the (hour, minute, second, Option<AM/PM>) tuple of `parse_time_components`.
+    fn into_components(self) -> (u32, u32, u32, Option<String>) {
+        (
+            self.hour12,
+            self.minute,
+            self.second.unwrap_or(0),
+            self.meridian.map(|meridian| meridian.as_str().to_string()),
+        )
+    }
+}
+
+/// Parses a time like `parse_time_components`, but returns what was written as a
+/// `ParsedTime`, so that e.g. "9:00AM" and "9:00:00AM" can be told apart.
+pub fn parse_time(time_str: &str) -> Result<ParsedTime, TimeError> {
+    parse_twelve_hour_fields(time_str, false)
+}
+
+/// Parses a 12-hour time like `parse_time_components` in a single pass over the input,
+/// returning the meridian as a `Meridian` so that a successful parse does not allocate.
+pub(crate) fn parse_twelve_hour_fields(
+    time_str: &str,
+    single_digit_minute: bool,
+) -> Result<ParsedTime, TimeError> {
+    if let Some((hour12, minute, _, meridian)) = time_keyword(time_str) {
+        return Ok(ParsedTime {
+            hour12,
+            minute,
+            second: None,
+            meridian: Some(meridian),
+        });
+    }
+
+    let original_time_str = time_str;
+    let mut time_part = time_str.trim();
+    let mut meridian = None;
+
+    if let Some((rest, dotted)) = strip_dotted_meridian(time_part) {
+        if rest.trim().is_empty() {
+            return Err(TimeError::InvalidFormat {
+                input: original_time_str.to_string(),
+                issue: FormatIssue::MeridianOnly,
+                position: None,
+            });
+        }
+        let (hour12, minute, second) = parse_clock_fields(
+            rest,
+            original_time_str,
+            ClockFormat::TwelveHour,
+            single_digit_minute,
+        )?;
+        return Ok(ParsedTime {
+            hour12,
+            minute,
+            second,
+            meridian: Some(dotted),
+        });
+    }
+
+    // Check for AM/PM suffix (case-insensitive)
+    if let Some((rest, suffix)) = split_meridian_suffix(time_part) {
+        if rest.is_empty() {
+            return Err(TimeError::InvalidFormat {
+                input: original_time_str.to_string(),
+                issue: FormatIssue::MeridianOnly,
+                position: None,
+            });
+        }
+        if rest.ends_with(' ') {
+            return Err(TimeError::InvalidFormat {
+                input: original_time_str.to_string(),
+                issue: FormatIssue::SpaceBeforeMeridian,
+                position: end_offset(rest.trim_end_matches(' '), original_time_str),
+            });
+        }
+        if !rest.ends_with(|c: char| c.is_ascii_digit()) {
+            return Err(TimeError::InvalidFormat {
+                input: original_time_str.to_string(),
+                issue: FormatIssue::MeridianAfterNonDigit,
+                position: end_offset(rest, original_time_str),
+            });
+        }
+        meridian = Some(suffix);
+        time_part = rest;
+    } else if let Some((rest, suffix)) = split_single_letter_meridian(time_part) {
+        meridian = Some(suffix);
+        time_part = rest;
+    }
+
+    let (hour12, minute, second) = parse_clock_fields(
+        time_part,
+        original_time_str,
+        ClockFormat::TwelveHour,
+        single_digit_minute,
+    )?;
+    Ok(ParsedTime {
+        hour12,
+        minute,
+        second,
+        meridian,
+    })
+}
+
+/// Splits a trailing "AM" or "PM", in any case, off `time_part`.
+fn split_meridian_suffix(time_part: &str) -> Option<(&str, Meridian)> {
+    let bytes = time_part.as_bytes();
+    let [.., first, last] = bytes else {
+        return None;
+    };
+    if !last.eq_ignore_ascii_case(&b'm') {
+        return None;
+    }
+    let meridian = match first.to_ascii_uppercase() {
+        b'A' => Meridian::Am,
+        b'P' => Meridian::Pm,
+        _ => return None,
+    };
+    // Both suffix bytes are ASCII, so this is a character boundary.
+    Some((&time_part[..bytes.len() - 2], meridian))
+}
+
+/// Splits a trailing "a" or "p", in any case, off `time_part` when a digit directly precedes
+/// it, as in the compact "9:00a" or "5:30P".
+fn split_single_letter_meridian(time_part: &str) -> Option<(&str, Meridian)> {
+    let rest = time_part.strip_suffix(['a', 'A', 'p', 'P'])?;
+    if !rest.ends_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let meridian = if time_part.ends_with(['a', 'A']) {
+        Meridian::Am
+    } else {
+        Meridian::Pm
+    };
+    Some((rest, meridian))
+}
+
+/// Parses a time like `parse_time_components`, but also accepts a single space before the
+/// AM/PM indicator, so "10:30 am", "10:30 Am" and "10:30AM" all parse identically.
+/// Two or more spaces before the indicator are rejected.
+///
+/// A single-digit minute is also accepted and read as written, so "9:5" is 9:05, not 9:50.
+/// A dot may stand in for the colon when exactly two digits follow it, so "9.00" and
+/// "5.30 pm" read as 9:00 and 5:30PM, while "9.5" and "9.00.15" are still rejected.
+/// With AM/PM the minutes may be left out, so "9AM" and "5 pm" read as 9:00AM and 5:00PM;
+/// a bare "9" is still rejected, since without AM/PM it looks like a number.
+pub fn parse_time_lenient(time_str: &str) -> Result<(u32, u32, u32, Option<String>), TimeError> {
+    parse_lenient_fields(time_str).map(ParsedTime::into_components)
+}
+
+/// The parser behind `parse_time_lenient`, returning a `ParsedTime`.
+pub(crate) fn parse_lenient_fields(time_str: &str) -> Result<ParsedTime, TimeError> {
+    let trimmed = time_str.trim();
+    if let Some((time_part, meridian)) = split_meridian_suffix(trimmed) {
+        if time_part.ends_with(' ') {
+            let without_space = time_part.trim_end_matches(' ');
+            if time_part.len() - without_space.len() > 1 {
+                return Err(TimeError::InvalidFormat {
+                    input: time_str.to_string(),
+                    issue: FormatIssue::MultipleSpacesBeforeMeridian,
+                    position: end_offset(without_space, time_str).map(|offset| offset + 1),
+                });
+            }
+            if !without_space.ends_with(|c: char| c.is_ascii_digit()) {
+                return Err(TimeError::InvalidFormat {
+                    input: time_str.to_string(),
+                    issue: FormatIssue::MeridianAfterNonDigit,
+                    position: end_offset(without_space, time_str),
+                });
+            }
+            let (hour12, minute, second) =
+                parse_lenient_clock(without_space, time_str, ClockFormat::TwelveHour)?;
+            return Ok(ParsedTime {
+                hour12,
+                minute,
+                second,
+                meridian: Some(meridian),
+            });
+        }
+    }
+    let (time_part, meridian) =
+        match split_meridian_suffix(trimmed).or_else(|| split_single_letter_meridian(trimmed)) {
+            Some((time_part, meridian)) => (time_part, Some(meridian)),
+            None => (trimmed, None),
+        };
+    if split_dotted_clock(time_part).is_some() || (meridian.is_some() && is_hour_only(time_part)) {
+        let (hour12, minute, second) =
+            parse_lenient_clock(time_part, time_str, ClockFormat::TwelveHour)?;
+        return Ok(ParsedTime {
+            hour12,
+            minute,
+            second,
+            meridian,
+        });
+    }
+    parse_twelve_hour_fields(time_str, true)
+}
+
+/// Parses a 24-hour time like `parse_time_components_24h`, but also accepts a dot between
+/// the hour and minute as `parse_time_lenient` does, so "17.30" reads as 17:30.
+pub(crate) fn parse_lenient_24h(time_str: &str) -> Result<(u32, u32, u32), TimeError> {
+    let time_part = time_str.trim();
+    if split_dotted_clock(time_part).is_none() {
+        return parse_time_components_24h(time_str);
+    }
+    parse_lenient_clock(time_part, time_str, ClockFormat::TwentyFourHour)
+        .map(|(hour, minute, second)| (hour, minute, second.unwrap_or(0)))
+}
+
+/// Parses the clock part of a lenient time: "H.MM" or "HH.MM" if `split_dotted_clock`
+/// accepts it, an hour alone as on the hour, otherwise colon-separated fields with a
+/// single-digit minute allowed. Callers only pass an hour alone when AM/PM follows it.
+fn parse_lenient_clock(
+    time_part: &str,
+    original_time_str: &str,
+    clock: ClockFormat,
+) -> Result<(u32, u32, Option<u32>), TimeError> {
+    match split_dotted_clock(time_part) {
+        Some((h_field, m_field)) => {
+            check_clock_fields(h_field, m_field, None, original_time_str, clock, false)
+        }
+        // The "00" stands in for the missing minute, which is always valid.
+        None if is_hour_only(time_part) => {
+            check_clock_fields(time_part, "00", None, original_time_str, clock, false)
+        }
+        None => parse_clock_fields(time_part, original_time_str, clock, true),
+    }
+}
+
+/// Returns whether `time_part` is just a one- or two-digit hour, as in the "9" of "9AM".
+fn is_hour_only(time_part: &str) -> bool {
+    (1..=2).contains(&time_part.len()) && time_part.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// Splits a time written with a dot instead of a colon, as in the European "9.00" or
+/// "17.30", into its hour and minute. The dot counts only when it is followed by exactly two
+/// digits and preceded by one or two, so a decimal hour such as "9.5" is not read as a time.
+fn split_dotted_clock(time_part: &str) -> Option<(&str, &str)> {
+    let (h_field, m_field) = time_part.split_once('.')?;
+    let all_digits = |field: &str| field.bytes().all(|b| b.is_ascii_digit());
+    let is_dotted = (1..=2).contains(&h_field.len())
+        && m_field.len() == 2
+        && all_digits(h_field)
+        && all_digits(m_field);
+    is_dotted.then_some((h_field, m_field))
+}
+
+/// Returns whether a 24-hour time is "24:00" or "24:00:00", the end of the day.
+pub(crate) fn is_end_of_day(time_str: &str) -> bool {
+    matches!(time_str.trim(), "24:00" | "24:00:00")
+}
+
+/// Parses a 24-hour time string (e.g., "09:00", "17:30", "17:30:15") into (hour, minute, second).
+/// Hours range from 0 to 23 and an AM/PM suffix is rejected.
+pub fn parse_time_components_24h(time_str: &str) -> Result<(u32, u32, u32), TimeError> {
+    let time_part = time_str.trim();
+    if let Some((rest, _)) =
+        split_meridian_suffix(time_part).or_else(|| split_single_letter_meridian(time_part))
+    {
+        return Err(TimeError::InvalidFormat {
+            input: time_str.to_string(),
+            issue: FormatIssue::MeridianIn24Hour,
+            position: end_offset(rest, time_str),
+        });
+    }
+
+    parse_clock_fields(time_part, time_str, ClockFormat::TwentyFourHour, false)
+        .map(|(hour, minute, second)| (hour, minute, second.unwrap_or(0)))
+}
+
+/// Splits "H:MM", "HH:MM" or "HH:MM:SS" into numeric hour, minute and second, validating
+/// the hour against `clock` and the minute and second against 0-59.
+///
+/// Spaces directly around a colon are ignored, so "9 : 00", "9: 00" and "9 :00" all read
+/// as "9:00". Spaces anywhere else, such as "9 0:00" or a trailing "10:30 ", still fail.
+/// The second is `None` when the time has no seconds field.
+fn parse_clock_fields(
+    time_part: &str,
+    original_time_str: &str,
+    clock: ClockFormat,
+    single_digit_minute: bool,
+) -> Result<(u32, u32, Option<u32>), TimeError> {
+    let mut fields = time_part.split(':');
+    let h_field = fields.next().unwrap_or_default();
+    let (Some(m_field), s_field, None) = (fields.next(), fields.next(), fields.next()) else {
+        return Err(TimeError::InvalidFormat {
+            input: original_time_str.to_string(),
+            issue: FormatIssue::ColonCount,
+            position: colon_count_position(time_part, original_time_str),
+        });
+    };
+    check_clock_fields(
+        h_field,
+        m_field,
+        s_field,
+        original_time_str,
+        clock,
+        single_digit_minute,
+    )
+}
+
+/// Validates the already split hour, minute and optional second fields of a time, as
+/// described for `parse_clock_fields`.
+fn check_clock_fields(
+    h_field: &str,
+    m_field: &str,
+    s_field: Option<&str>,
+    original_time_str: &str,
+    clock: ClockFormat,
+    single_digit_minute: bool,
+) -> Result<(u32, u32, Option<u32>), TimeError> {
+    let h_str = h_field.trim_end_matches(' ');
+    let m_str = m_field.trim_start_matches(' ');
+    let m_str = if s_field.is_some() {
+        m_str.trim_end_matches(' ')
+    } else {
+        m_str
+    };
+    let s_str = s_field.map(|s| s.trim_start_matches(' '));
+
+    let hour_error = |issue: FieldIssue| TimeError::InvalidHour {
+        input: original_time_str.to_string(),
+        hour: h_str.to_string(),
+        clock,
+        position: field_position(h_str, original_time_str, &issue),
+        issue,
+    };
+    let minute_error = |issue: FieldIssue| TimeError::InvalidMinute {
+        input: original_time_str.to_string(),
+        minute: m_str.to_string(),
+        position: field_position(m_str, original_time_str, &issue),
+        issue,
+    };
+    let second_error = |second: &str, issue: FieldIssue| TimeError::InvalidSecond {
+        input: original_time_str.to_string(),
+        second: second.to_string(),
+        position: field_position(second, original_time_str, &issue),
+        issue,
+    };
+
+    if h_str.is_empty() || !(1..=2).contains(&h_str.len()) {
+        return Err(hour_error(FieldIssue::Length));
+    }
+    let minute_len_ok = m_str.len() == 2 || (single_digit_minute && m_str.len() == 1);
+    if !minute_len_ok {
+        return Err(minute_error(FieldIssue::Length));
+    }
+
+    let hour: u32 = h_str
+        .parse()
+        .map_err(|_| hour_error(FieldIssue::NotANumber))?;
+    let minute: u32 = m_str
+        .parse()
+        .map_err(|_| minute_error(FieldIssue::NotANumber))?;
+
+    let second: Option<u32> = match s_str {
+        None => None,
+        Some(s_str) => {
+            if s_str.len() != 2 {
+                return Err(second_error(s_str, FieldIssue::Length));
+            }
+            Some(
+                s_str
+                    .parse()
+                    .map_err(|_| second_error(s_str, FieldIssue::NotANumber))?,
+            )
+        }
+    };
+
+    if !clock.hour_range().contains(&hour) {
+        return Err(hour_error(FieldIssue::OutOfRange { value: hour }));
+    }
+    if minute > 59 {
+        return Err(minute_error(FieldIssue::OutOfRange { value: minute }));
+    }
+    if let Some(second) = second.filter(|second| *second > 59) {
+        return Err(second_error(
+            s_str.unwrap_or_default(),
+            FieldIssue::OutOfRange { value: second },
+        ));
+    }
+
+    Ok((hour, minute, second))
+}
+
+/// Returns the byte offset just past `part` within `whole`.
+fn end_offset(part: &str, whole: &str) -> Option<usize> {
+    offset_in(part, whole).map(|offset| offset + part.len())
+}
+
+/// Returns the offset of the third colon of `time_part` within `original_time_str`, which is
+/// where a time with too many colons goes wrong. A time with too few has no such place.
+fn colon_count_position(time_part: &str, original_time_str: &str) -> Option<usize> {
+    let (index, _) = time_part.match_indices(':').nth(2)?;
+    offset_in(time_part, original_time_str).map(|offset| offset + index)
+}
+
+/// Returns where a `field` with `issue` went wrong within `original_time_str`: its first
+/// non-digit if it is not a number, otherwise its start.
+fn field_position(field: &str, original_time_str: &str, issue: &FieldIssue) -> Option<usize> {
+    let start = offset_in(field, original_time_str)?;
+    match issue {
+        FieldIssue::NotANumber => {
+            Some(start + field.find(|c: char| !c.is_ascii_digit()).unwrap_or(0))
+        }
+        FieldIssue::Length | FieldIssue::OutOfRange { .. } => Some(start),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use alloc::vec::Vec;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_parse_time_components_valid() {
+        assert_eq!(
+            parse_time_components("09:00AM"),
+            Ok((9, 0, 0, Some("AM".to_string())))
+        );
+        assert_eq!(
+            parse_time_components("9:00am"),
+            Ok((9, 0, 0, Some("AM".to_string())))
+        );
+        assert_eq!(
+            parse_time_components("12:30PM"),
+            Ok((12, 30, 0, Some("PM".to_string())))
+        );
+        assert_eq!(
+            parse_time_components("01:15pm"),
+            Ok((1, 15, 0, Some("PM".to_string())))
+        );
+        assert_eq!(parse_time_components("09:00"), Ok((9, 0, 0, None)));
+        assert_eq!(parse_time_components("9:00"), Ok((9, 0, 0, None)));
+        assert_eq!(parse_time_components("12:00"), Ok((12, 0, 0, None)));
+        assert_eq!(
+            parse_time_components(" 07:00AM "),
+            Ok((7, 0, 0, Some("AM".to_string())))
+        );
+        assert_eq!(parse_time_components("7:00"), Ok((7, 0, 0, None)));
+    }
+
+    #[test]
+    fn test_parse_time_components_invalid_format() {
+        assert!(parse_time_components("900AM").is_err());
+        assert!(parse_time_components("09:00XM").is_err());
+        assert!(parse_time_components("09:00PMM").is_err());
+        assert!(parse_time_components("090:00AM").is_err());
+        assert!(parse_time_components("09:0AM").is_err());
+        assert!(parse_time_components("09:000AM").is_err());
+        assert!(parse_time_components(":00AM").is_err());
+        assert!(parse_time_components("09:AM").is_err());
+        assert!(parse_time_components("9").is_err());
+        assert!(parse_time_components("9AM").is_err());
+        assert!(parse_time_components("AM").is_err());
+        assert!(parse_time_components("").is_err());
+        assert!(parse_time_components("10:30 AM").is_err());
+    }
+
+    #[test]
+    fn test_parse_time_components_invalid_values() {
+        assert!(parse_time_components("00:00AM").is_err());
+        assert!(parse_time_components("13:00AM").is_err());
+        assert!(parse_time_components("09:60AM").is_err());
+        assert!(parse_time_components("AA:00AM").is_err());
+        assert!(parse_time_components("09:BBAM").is_err());
+    }
+
+    #[test]
+    fn test_parse_time_components_error_variants() {
+        assert!(matches!(
+            parse_time_components("AM"),
+            Err(TimeError::InvalidFormat {
+                issue: FormatIssue::MeridianOnly,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_time_components("900AM"),
+            Err(TimeError::InvalidFormat {
+                issue: FormatIssue::ColonCount,
+                ..
+            })
+        ));
+        assert_eq!(
+            parse_time_components("13:00AM"),
+            Err(TimeError::InvalidHour {
+                input: "13:00AM".to_string(),
+                hour: "13".to_string(),
+                clock: ClockFormat::TwelveHour,
+                issue: FieldIssue::OutOfRange { value: 13 },
+                position: Some(0),
+            })
+        );
+        assert!(matches!(
+            parse_time_components("AA:00AM"),
+            Err(TimeError::InvalidHour {
+                issue: FieldIssue::NotANumber,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_time_components("09:60AM"),
+            Err(TimeError::InvalidMinute {
+                issue: FieldIssue::OutOfRange { value: 60 },
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_time_components("09:00:5AM"),
+            Err(TimeError::InvalidSecond {
+                issue: FieldIssue::Length,
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_time_components_24h("1:00PM"),
+            Err(TimeError::InvalidFormat {
+                issue: FormatIssue::MeridianIn24Hour,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_time_components_space_before_meridian() {
+        let error = parse_time_components("10:30 AM").unwrap_err();
+        assert_eq!(
+            error,
+            TimeError::InvalidFormat {
+                input: "10:30 AM".to_string(),
+                issue: FormatIssue::SpaceBeforeMeridian,
+                position: Some(5),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "Invalid time format: '10:30 AM'. Remove the space before AM/PM, or use lenient parsing."
+        );
+        assert!(matches!(
+            parse_time_components("10:30  pm"),
+            Err(TimeError::InvalidFormat {
+                issue: FormatIssue::SpaceBeforeMeridian,
+                ..
+            })
+        ));
+        assert!(parse_time_lenient("10:30 AM").is_ok());
+    }
+
+    #[test]
+    fn test_parse_time_components_with_seconds() {
+        assert_eq!(
+            parse_time_components("09:00:30AM"),
+            Ok((9, 0, 30, Some("AM".to_string())))
+        );
+        assert_eq!(parse_time_components("9:01:00"), Ok((9, 1, 0, None)));
+        assert_eq!(
+            parse_time_components("12:59:59pm"),
+            Ok((12, 59, 59, Some("PM".to_string())))
+        );
+        assert!(parse_time_components("09:00:60AM").is_err());
+        assert!(parse_time_components("09:00:5AM").is_err());
+        assert!(parse_time_components("09:00:005AM").is_err());
+        assert!(parse_time_components("09:00:XXAM").is_err());
+        assert!(parse_time_components("09:00:00:00AM").is_err());
+        assert!(parse_time_components("09:00:AM").is_err());
+    }
+
+    #[test]
+    fn test_parse_time_components_spaces_around_colon() {
+        let nine = Ok((9, 0, 0, None));
+        assert_eq!(parse_time_components("9 : 00"), nine);
+        assert_eq!(parse_time_components("9: 00"), nine);
+        assert_eq!(parse_time_components("9 :00"), nine);
+        assert_eq!(
+            parse_time_components("9 : 00AM"),
+            Ok((9, 0, 0, Some("AM".to_string())))
+        );
+        assert_eq!(
+            parse_time_components("9 : 00 : 30PM"),
+            Ok((9, 0, 30, Some("PM".to_string())))
+        );
+        assert_eq!(
+            parse_time_lenient(" 9 : 00 AM "),
+            Ok((9, 0, 0, Some("AM".to_string())))
+        );
+        assert!(parse_time_components("9 0:00").is_err());
+        assert!(parse_time_components("9:0 0").is_err());
+        assert!(parse_time_components("9 : 00 AM").is_err());
+        assert!(parse_time_components(" : 00").is_err());
+    }
+
+    #[test]
+    fn test_parse_time_components_keywords() {
+        let noon = Ok((12, 0, 0, Some("PM".to_string())));
+        let midnight = Ok((12, 0, 0, Some("AM".to_string())));
+        assert_eq!(parse_time_components("noon"), noon);
+        assert_eq!(parse_time_components(" NOON "), noon);
+        assert_eq!(parse_time_components("Midnight"), midnight);
+        assert_eq!(parse_time_lenient("noon"), noon);
+        assert!(parse_time_components("noon1").is_err());
+        assert!(parse_time_components("afternoon").is_err());
+        assert!(parse_time_components("12:00noon").is_err());
+    }
+
+    #[test]
+    fn test_parse_time_components_dotted_meridian() {
+        assert_eq!(
+            parse_time_components("9:00 a.m."),
+            Ok((9, 0, 0, Some("AM".to_string())))
+        );
+        assert_eq!(
+            parse_time_components("5:30 p.m."),
+            Ok((5, 30, 0, Some("PM".to_string())))
+        );
+        assert_eq!(
+            parse_time_components("9:00p.m"),
+            Ok((9, 0, 0, Some("PM".to_string())))
+        );
+        assert_eq!(
+            parse_time_components("9:00:15P.M."),
+            Ok((9, 0, 15, Some("PM".to_string())))
+        );
+        assert!(parse_time_components("9:00  p.m.").is_err());
+        assert_eq!(
+            parse_time_components("p.m."),
+            Err(TimeError::InvalidFormat {
+                input: "p.m.".to_string(),
+                issue: FormatIssue::MeridianOnly,
+                position: None,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_time_lenient() {
+        let expected = Ok((10, 30, 0, Some("AM".to_string())));
+        assert_eq!(parse_time_lenient("10:30 am"), expected);
+        assert_eq!(parse_time_lenient("10:30 Am"), expected);
+        assert_eq!(parse_time_lenient("10:30AM"), expected);
+        assert_eq!(parse_time_lenient(" 10:30 AM "), expected);
+        assert_eq!(
+            parse_time_lenient("9:00:15 pm"),
+            Ok((9, 0, 15, Some("PM".to_string())))
+        );
+        assert_eq!(parse_time_lenient("10:30"), Ok((10, 30, 0, None)));
+        assert!(matches!(
+            parse_time_lenient("10:30  am"),
+            Err(TimeError::InvalidFormat {
+                issue: FormatIssue::MultipleSpacesBeforeMeridian,
+                ..
+            })
+        ));
+        assert!(parse_time_lenient(" AM").is_err());
+        assert_eq!(
+            parse_time_lenient("10:3 AM"),
+            Ok((10, 3, 0, Some("AM".to_string())))
+        );
+        assert!(parse_time_components("10:30 am").is_err());
+    }
+
+    #[test]
+    fn test_parse_time_lenient_single_digit_minute() {
+        assert_eq!(parse_time_lenient("9:5"), Ok((9, 5, 0, None)));
+        assert_eq!(
+            parse_time_lenient("9:5PM"),
+            Ok((9, 5, 0, Some("PM".to_string())))
+        );
+        assert_eq!(
+            parse_time_lenient("9:5:30 pm"),
+            Ok((9, 5, 30, Some("PM".to_string())))
+        );
+        assert!(matches!(
+            parse_time_lenient("9:"),
+            Err(TimeError::InvalidMinute {
+                issue: FieldIssue::Length,
+                ..
+            })
+        ));
+        assert!(parse_time_lenient("9:123").is_err());
+        assert!(matches!(
+            parse_time_components("9:5"),
+            Err(TimeError::InvalidMinute {
+                issue: FieldIssue::Length,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_time_components_24h() {
+        assert_eq!(parse_time_components_24h("00:00"), Ok((0, 0, 0)));
+        assert_eq!(parse_time_components_24h("9:05"), Ok((9, 5, 0)));
+        assert_eq!(parse_time_components_24h(" 13:00 "), Ok((13, 0, 0)));
+        assert_eq!(parse_time_components_24h("23:59"), Ok((23, 59, 0)));
+        assert_eq!(parse_time_components_24h("23:59:59"), Ok((23, 59, 59)));
+        assert!(parse_time_components_24h("24:00").is_err());
+        assert!(parse_time_components_24h("12:60").is_err());
+        assert!(parse_time_components_24h("1:00PM").is_err());
+        assert!(parse_time_components_24h("09:00am").is_err());
+        assert!(parse_time_components_24h("0900").is_err());
+    }
+
+    /// The `Vec`-based parser that `parse_twelve_hour_fields` replaced, kept to check that
+    /// the single-pass parser returns identical results.
+    fn reference_parse_twelve_hour(
+        time_str: &str,
+        single_digit_minute: bool,
+    ) -> Result<(u32, u32, u32, Option<String>), TimeError> {
+        if let Some((hour12, minute, second, ampm)) = time_keyword(time_str) {
+            return Ok((hour12, minute, second, Some(ampm.as_str().to_string())));
+        }
+
+        let original_time_str = time_str;
+        let mut time_part = time_str.trim();
+        let mut ampm_opt: Option<String> = None;
+
+        if let Some((rest, meridian)) = strip_dotted_meridian(time_part) {
+            if rest.trim().is_empty() {
+                return Err(TimeError::InvalidFormat {
+                    input: original_time_str.to_string(),
+                    issue: FormatIssue::MeridianOnly,
+                    position: None,
+                });
+            }
+            let (hour12, minute, second) = reference_parse_clock_fields(
+                rest,
+                original_time_str,
+                ClockFormat::TwelveHour,
+                single_digit_minute,
+            )?;
+            return Ok((hour12, minute, second, Some(meridian.as_str().to_string())));
+        }
+
+        // Check for AM/PM suffix (case-insensitive)
+        if time_part.len() >= 2 {
+            let potential_ampm = &time_part[time_part.len() - 2..];
+            if potential_ampm.eq_ignore_ascii_case("AM")
+                || potential_ampm.eq_ignore_ascii_case("PM")
+            {
+                if time_part.len() > 2 {
+                    ampm_opt = Some(potential_ampm.to_uppercase());
+                    time_part = &time_part[..time_part.len() - 2];
+                    if time_part.ends_with(' ') {
+                        return Err(TimeError::InvalidFormat {
+                            input: original_time_str.to_string(),
+                            issue: FormatIssue::SpaceBeforeMeridian,
+                            position: end_offset(
+                                time_part.trim_end_matches(' '),
+                                original_time_str,
+                            ),
+                        });
+                    }
+                    if !time_part.ends_with(|c: char| c.is_ascii_digit()) {
+                        return Err(TimeError::InvalidFormat {
+                            input: original_time_str.to_string(),
+                            issue: FormatIssue::MeridianAfterNonDigit,
+                            position: end_offset(time_part, original_time_str),
+                        });
+                    }
+                } else {
+                    return Err(TimeError::InvalidFormat {
+                        input: original_time_str.to_string(),
+                        issue: FormatIssue::MeridianOnly,
+                        position: None,
+                    });
+                }
+            }
+        }
+
+        if ampm_opt.is_none() && time_part.len() >= 2 {
+            let (rest, letter) = time_part.split_at(time_part.len() - 1);
+            let is_letter_meridian =
+                letter.eq_ignore_ascii_case("a") || letter.eq_ignore_ascii_case("p");
+            if is_letter_meridian && rest.ends_with(|c: char| c.is_ascii_digit()) {
+                ampm_opt = Some(format!("{}M", letter.to_uppercase()));
+                time_part = rest;
+            }
+        }
+
+        let (hour12, minute, second) = reference_parse_clock_fields(
+            time_part,
+            original_time_str,
+            ClockFormat::TwelveHour,
+            single_digit_minute,
+        )?;
+        Ok((hour12, minute, second, ampm_opt))
+    }
+
+    fn reference_parse_clock_fields(
+        time_part: &str,
+        original_time_str: &str,
+        clock: ClockFormat,
+        single_digit_minute: bool,
+    ) -> Result<(u32, u32, u32), TimeError> {
+        let mut parts: Vec<&str> = time_part.split(':').collect();
+        let last = parts.len() - 1;
+        for (index, part) in parts.iter_mut().enumerate() {
+            if index > 0 {
+                *part = part.trim_start_matches(' ');
+            }
+            if index < last {
+                *part = part.trim_end_matches(' ');
+            }
+        }
+        if !(2..=3).contains(&parts.len()) {
+            return Err(TimeError::InvalidFormat {
+                input: original_time_str.to_string(),
+                issue: FormatIssue::ColonCount,
+                position: colon_count_position(time_part, original_time_str),
+            });
+        }
+
+        let h_str = parts[0];
+        let m_str = parts[1];
+        let s_str = parts.get(2).copied();
+
+        let hour_error = |issue: FieldIssue| TimeError::InvalidHour {
+            input: original_time_str.to_string(),
+            hour: h_str.to_string(),
+            clock,
+            position: field_position(h_str, original_time_str, &issue),
+            issue,
+        };
+        let minute_error = |issue: FieldIssue| TimeError::InvalidMinute {
+            input: original_time_str.to_string(),
+            minute: m_str.to_string(),
+            position: field_position(m_str, original_time_str, &issue),
+            issue,
+        };
+        let second_error = |second: &str, issue: FieldIssue| TimeError::InvalidSecond {
+            input: original_time_str.to_string(),
+            second: second.to_string(),
+            position: field_position(second, original_time_str, &issue),
+            issue,
+        };
+
+        if h_str.is_empty() || !(1..=2).contains(&h_str.len()) {
+            return Err(hour_error(FieldIssue::Length));
+        }
+        let minute_len_ok = m_str.len() == 2 || (single_digit_minute && m_str.len() == 1);
+        if !minute_len_ok {
+            return Err(minute_error(FieldIssue::Length));
+        }
+
+        let hour: u32 = h_str
+            .parse()
+            .map_err(|_| hour_error(FieldIssue::NotANumber))?;
+        let minute: u32 = m_str
+            .parse()
+            .map_err(|_| minute_error(FieldIssue::NotANumber))?;
+
+        let second: u32 = match s_str {
+            None => 0,
+            Some(s_str) => {
+                if s_str.len() != 2 {
+                    return Err(second_error(s_str, FieldIssue::Length));
+                }
+                s_str
+                    .parse()
+                    .map_err(|_| second_error(s_str, FieldIssue::NotANumber))?
+            }
+        };
+
+        if !clock.hour_range().contains(&hour) {
+            return Err(hour_error(FieldIssue::OutOfRange { value: hour }));
+        }
+        if minute > 59 {
+            return Err(minute_error(FieldIssue::OutOfRange { value: minute }));
+        }
+        if second > 59 {
+            return Err(second_error(
+                s_str.unwrap_or_default(),
+                FieldIssue::OutOfRange { value: second },
+            ));
+        }
+
+        Ok((hour, minute, second))
+    }
+
+    proptest! {
+        #[test]
+        fn prop_single_pass_parser_matches_reference(
+            time_str in "[0-9: apmAPM.x]{0,12}",
+            single_digit_minute in any::<bool>(),
+        ) {
+            prop_assert_eq!(
+                parse_twelve_hour(&time_str, single_digit_minute),
+                reference_parse_twelve_hour(&time_str, single_digit_minute)
+            );
+            for clock in [ClockFormat::TwelveHour, ClockFormat::TwentyFourHour] {
+                prop_assert_eq!(
+                    parse_clock_fields(&time_str, &time_str, clock, single_digit_minute)
+                        .map(|(hour, minute, second)| (hour, minute, second.unwrap_or(0))),
+                    reference_parse_clock_fields(&time_str, &time_str, clock, single_digit_minute)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_error_positions() {
+        let position = |result: Result<(u32, u32, u32, Option<String>), TimeError>| {
+            result.unwrap_err().position()
+        };
+        assert_eq!(position(parse_time_components("09:6?")), Some(4));
+        assert_eq!(position(parse_time_components(" 9:0AM")), Some(3));
+        assert_eq!(position(parse_time_components("x9:00")), Some(0));
+        assert_eq!(position(parse_time_components("9:00:6x")), Some(6));
+        assert_eq!(position(parse_time_components("9:00:00:00")), Some(7));
+        assert_eq!(position(parse_time_components("900")), None);
+        assert_eq!(position(parse_time_components("10:30  pm")), Some(5));
+        assert_eq!(position(parse_time_lenient("10:30  pm")), Some(6));
+        assert_eq!(
+            parse_time_components_24h(" 9:00PM").unwrap_err().position(),
+            Some(5)
+        );
+    }
+
+    #[test]
+    fn test_parse_time_components_meridian_after_non_digit() {
+        for input in ["FOOAM:PM", "9:00XAM", "9:00:pm", "9:00.AM", "AM:AM"] {
+            assert_eq!(
+                parse_time_components(input).map_err(|e| match e {
+                    TimeError::InvalidFormat { issue, .. } => Some(issue),
+                    _ => None,
+                }),
+                Err(Some(FormatIssue::MeridianAfterNonDigit)),
+                "{}",
+                input
+            );
+        }
+        assert_eq!(
+            parse_time_components("9:00XAM").unwrap_err().position(),
+            Some(5)
+        );
+        assert!(matches!(
+            parse_time_lenient("9:00X am"),
+            Err(TimeError::InvalidFormat {
+                issue: FormatIssue::MeridianAfterNonDigit,
+                position: Some(5),
+                ..
+            })
+        ));
+        assert_eq!(
+            parse_time_components("9:00am"),
+            Ok((9, 0, 0, Some("AM".to_string())))
+        );
+        assert_eq!(
+            parse_time_components("9:00:30Pm"),
+            Ok((9, 0, 30, Some("PM".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_parse_time_components_single_letter_meridian() {
+        assert_eq!(
+            parse_time_components("9:00a"),
+            Ok((9, 0, 0, Some("AM".to_string())))
+        );
+        assert_eq!(
+            parse_time_components("5:30P"),
+            Ok((5, 30, 0, Some("PM".to_string())))
+        );
+        assert_eq!(
+            parse_time_components("5:30:15p"),
+            Ok((5, 30, 15, Some("PM".to_string())))
+        );
+        assert_eq!(
+            parse_time_components("9:00am"),
+            Ok((9, 0, 0, Some("AM".to_string())))
+        );
+        assert!(parse_time_components("9:00 a").is_err());
+        assert!(parse_time_components("9:00xa").is_err());
+        assert!(parse_time_components("a").is_err());
+        assert!(matches!(
+            parse_time_components_24h("17:30p"),
+            Err(TimeError::InvalidFormat {
+                issue: FormatIssue::MeridianIn24Hour,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_time() {
+        assert_eq!(
+            parse_time("9:05PM"),
+            Ok(ParsedTime {
+                hour12: 9,
+                minute: 5,
+                second: None,
+                meridian: Some(Meridian::Pm),
+            })
+        );
+        assert_eq!(
+            parse_time(" 09:05:00 "),
+            Ok(ParsedTime {
+                hour12: 9,
+                minute: 5,
+                second: Some(0),
+                meridian: None,
+            })
+        );
+        assert_eq!(
+            parse_time("noon").map(|parsed| parsed.meridian),
+            Ok(Some(Meridian::Pm))
+        );
+        assert!(parse_time("13:00").is_err());
+    }
+
+    #[test]
+    fn test_parse_time_lenient_dot_delimiter() {
+        assert_eq!(parse_time_lenient("9.00"), Ok((9, 0, 0, None)));
+        assert_eq!(
+            parse_time_lenient("5.30PM"),
+            Ok((5, 30, 0, Some("PM".to_string())))
+        );
+        assert_eq!(
+            parse_time_lenient("5.30 pm"),
+            Ok((5, 30, 0, Some("PM".to_string())))
+        );
+        assert_eq!(
+            parse_time_lenient("5.30p"),
+            Ok((5, 30, 0, Some("PM".to_string())))
+        );
+        assert_eq!(parse_lenient_24h("17.30"), Ok((17, 30, 0)));
+        assert_eq!(parse_lenient_24h("17:30:15"), Ok((17, 30, 15)));
+        for input in ["9.5", "9.000", "9.00.15", ".30", "123.00", "9.3x"] {
+            assert!(parse_time_lenient(input).is_err(), "{}", input);
+        }
+        assert!(matches!(
+            parse_time_lenient("13.00"),
+            Err(TimeError::InvalidHour {
+                issue: FieldIssue::OutOfRange { value: 13 },
+                position: Some(0),
+                ..
+            })
+        ));
+        assert!(parse_time_components("9.00").is_err());
+        assert!(parse_time_components_24h("17.30").is_err());
+    }
+
+    #[test]
+    fn test_parse_time_lenient_hour_only() {
+        assert_eq!(
+            parse_time_lenient("9AM"),
+            Ok((9, 0, 0, Some("AM".to_string())))
+        );
+        assert_eq!(
+            parse_time_lenient("12 pm"),
+            Ok((12, 0, 0, Some("PM".to_string())))
+        );
+        assert_eq!(
+            parse_time_lenient("5p"),
+            Ok((5, 0, 0, Some("PM".to_string())))
+        );
+        assert_eq!(
+            parse_lenient_fields("9AM").map(|parsed| parsed.second),
+            Ok(None)
+        );
+        for input in ["9", "09", "123AM", "AM", "9  AM", "xAM"] {
+            assert!(parse_time_lenient(input).is_err(), "{}", input);
+        }
+        assert!(matches!(
+            parse_time_lenient("13PM"),
+            Err(TimeError::InvalidHour {
+                issue: FieldIssue::OutOfRange { value: 13 },
+                position: Some(0),
+                ..
+            })
+        ));
+        assert!(parse_time_components("9AM").is_err());
+        assert!(parse_lenient_24h("17").is_err());
+    }
+}
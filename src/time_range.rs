@@ -0,0 +1,386 @@
+use crate::time_conversion::ImplicitMeridians;
+use crate::time_difference::{calculate_range, resolve_sides, split_range};
+use crate::time_of_day::{TimeOfDay, SECONDS_PER_DAY};
+use crate::time_span::TimeSpan;
+use crate::TimeError;
+use alloc::format;
+use alloc::string::{String, ToString};
+
+/// A single-day range between two times of day, parsed from a string such as "9:00AM-5:30PM"
+/// with the same rules as `calculate_time_difference_from_range_str`. Ranges compare and hash
+/// by their times, so "9:00AM-5:00PM" and "09:00AM-05:00PM" are the same key in a `HashSet`.
+/// A 24-hour range may end at "24:00", the end of the day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimeRange {
+    start: TimeOfDay,
+    end: TimeOfDay,
+    end_of_day: bool,
+}
+
+impl TimeRange {
+    /// Builds a range from times already parsed, e.g. from a time picker, so that its duration
+    /// can be taken without formatting and parsing a range string. An `end` before `start` is
+    /// `TimeError::EndBeforeStart`, with both times in canonical form.
+    pub fn new(start: TimeOfDay, end: TimeOfDay) -> Result<Self, TimeError> {
+        if end < start {
+            return Err(TimeError::EndBeforeStart {
+                start: start.to_string(),
+                start_interpreted: start.to_string(),
+                start_minutes: start.minutes_from_midnight(),
+                end: end.to_string(),
+                end_interpreted: end.to_string(),
+                end_minutes: end.minutes_from_midnight(),
+            });
+        }
+        Ok(TimeRange {
+            start,
+            end,
+            end_of_day: false,
+        })
+    }
+
+    /// Returns the start of the range.
+    pub fn start(&self) -> TimeOfDay {
+        self.start
+    }
+
+    /// Returns the end of the range, which is never before the start unless the range ends
+    /// at "24:00", when it is midnight.
+    pub fn end(&self) -> TimeOfDay {
+        self.end
+    }
+
+    /// Returns whether the range ends at "24:00", the end of the day.
+    pub fn ends_at_end_of_day(&self) -> bool {
+        self.end_of_day
+    }
+
+    /// Returns the time between the start and the end.
+    pub fn span(&self) -> TimeSpan {
+        TimeSpan::from_seconds(self.end_seconds() - self.start.seconds_from_midnight())
+    }
+
+    /// Returns the length of the range in fractional hours, as
+    /// `calculate_time_difference_from_range_str` does for the range's string.
+    pub fn duration_hours(&self) -> f64 {
+        self.span().as_hours_f64()
+    }
+
+    /// Returns the time shared by this range and `other`. Ranges include their start but
+    /// not their end, so ranges that only touch, like 9:00AM-12:00PM and 12:00PM-2:00PM,
+    /// share nothing.
+    pub fn overlap(&self, other: &TimeRange) -> TimeSpan {
+        let start = self.start.max(other.start).seconds_from_midnight();
+        let end = self.end_seconds().min(other.end_seconds());
+        if end <= start {
+            return TimeSpan::from_seconds(0);
+        }
+        TimeSpan::from_seconds(end - start)
+    }
+
+    fn end_seconds(&self) -> u32 {
+        if self.end_of_day {
+            SECONDS_PER_DAY
+        } else {
+            self.end.seconds_from_midnight()
+        }
+    }
+}
+
+/// Returns whether two ranges share any time, e.g. "9:00AM-12:00PM" and "11:00AM-2:00PM".
+/// Ranges that only touch at an endpoint do not overlap.
+pub fn ranges_overlap(a: &str, b: &str) -> Result<bool, TimeError> {
+    overlap_span(a, b).map(|span| span.total_seconds() > 0)
+}
+
+/// Returns the whole minutes two ranges share, e.g. 60 for "9:00AM-12:00PM" and
+/// "11:00AM-2:00PM". Ranges that only touch at an endpoint share 0 minutes.
+pub fn overlap_minutes(a: &str, b: &str) -> Result<u32, TimeError> {
+    overlap_span(a, b).map(|span| span.total_minutes())
+}
+
+/// Returns the hours of a range that fall within a window, e.g. "8:00AM-6:00PM" clamped
+/// to 9:00AM-5:00PM gives 8.0. The window's times are read like a range, so "9:00" and
+/// "5:00" mean 9:00AM and 5:00PM. A range entirely outside the window gives 0.0.
+pub fn clamp_to_window(
+    range_str: &str,
+    window_start: &str,
+    window_end: &str,
+) -> Result<f64, TimeError> {
+    let range: TimeRange = range_str.parse()?;
+    let window: TimeRange = format!("{}-{}", window_start, window_end).parse()?;
+    Ok(range.overlap(&window).as_hours_f64())
+}
+
+/// Like `clamp_to_window`, but a range with no time inside the window is an error.
+pub fn clamp_to_window_or_error(
+    range_str: &str,
+    window_start: &str,
+    window_end: &str,
+) -> Result<f64, TimeError> {
+    let hours = clamp_to_window(range_str, window_start, window_end)?;
+    if hours == 0.0 {
+        return Err(TimeError::OutsideWindow {
+            input: range_str.to_string(),
+            window: format!("{}-{}", window_start, window_end),
+        });
+    }
+    Ok(hours)
+}
+
+/// Rewrites a range in canonical form for comparison or deduplication, e.g.
+/// " 9:00 am - 5:30 pm " as "09:00AM-05:30PM": each time zero-padded with an uppercase AM/PM,
+/// joined by a single '-'. Times are read leniently, allowing a space before AM/PM or a dot in
+/// place of the colon, as in "9.00-17.30", and otherwise as by
+/// `calculate_time_difference_from_range_str`, so the end must not be before the start.
+/// Normalizing a normalized range returns it unchanged.
+pub fn normalize_range(range_str: &str) -> Result<String, TimeError> {
+    let (raw_start, raw_end) = split_range(range_str, "-")?;
+    let range = resolve_sides(
+        range_str,
+        raw_start,
+        raw_end,
+        ImplicitMeridians::default(),
+        true,
+    )?;
+    range.span()?;
+    Ok(TimeRange {
+        start: range.start,
+        end: range.end_time(),
+        end_of_day: range.ends_at_end_of_day(),
+    }
+    .to_string())
+}
+
+/// Returns the time halfway through a range, in canonical form, e.g. "01:00PM" for
+/// "9:00AM-5:00PM". The midpoint is rounded to the nearest minute, with a half minute rounding
+/// up, so "9:00AM-9:03AM" gives "09:02AM".
+pub fn midpoint(range_str: &str) -> Result<String, TimeError> {
+    let range: TimeRange = range_str.parse()?;
+    let middle = range.start.seconds_from_midnight() + range.span().total_seconds() / 2;
+    // Rounding up can only pass 11:59PM when the range ends in its last minute.
+    let minute_of_day = ((middle + 30) / 60).min(24 * 60 - 1);
+    let time = TimeOfDay::from_hms(minute_of_day / 60, minute_of_day % 60, 0)
+        .expect("midpoint is within the day");
+    Ok(time.to_string())
+}
+
+fn overlap_span(a: &str, b: &str) -> Result<TimeSpan, TimeError> {
+    let a: TimeRange = a.parse()?;
+    let b: TimeRange = b.parse()?;
+    Ok(a.overlap(&b))
+}
+
+impl core::str::FromStr for TimeRange {
+    type Err = TimeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let range = calculate_range(s)?;
+        Ok(TimeRange {
+            start: range.start,
+            end: range.end,
+            end_of_day: range.end_of_day,
+        })
+    }
+}
+
+/// Formats the range in canonical form, e.g. "09:00AM-05:30PM", which parses back to the same
+/// range. A range ending at "24:00" has no 12-hour form, so it is written as 24-hour, e.g.
+/// "22:00-24:00".
+impl core::fmt::Display for TimeRange {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.end_of_day {
+            return write!(f, "{}-24:00", self.start.format_24h());
+        }
+        write!(f, "{}-{}", self.start, self.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_range_parse() {
+        let range: TimeRange = "9:00-5:30".parse().unwrap();
+        assert_eq!(range.start(), TimeOfDay::parse("9:00AM").unwrap());
+        assert_eq!(range.end(), TimeOfDay::parse("5:30PM").unwrap());
+        assert_eq!(range.span(), TimeSpan::from_minutes(510));
+        assert_eq!(range.duration_hours(), 8.5);
+        assert!("5:00PM-9:00AM".parse::<TimeRange>().is_err());
+        assert!("9:00AM".parse::<TimeRange>().is_err());
+    }
+
+    #[test]
+    fn test_time_range_display_round_trip() {
+        let range: TimeRange = "9:00am-5:30pm".parse().unwrap();
+        assert_eq!(range.to_string(), "09:00AM-05:30PM");
+        assert_eq!(range.to_string().parse::<TimeRange>(), Ok(range));
+        let with_seconds: TimeRange = "12:00:30AM-noon".parse().unwrap();
+        assert_eq!(with_seconds.to_string(), "12:00:30AM-12:00PM");
+        assert_eq!(with_seconds.to_string().parse(), Ok(with_seconds));
+    }
+
+    #[test]
+    fn test_overlap() {
+        assert_eq!(overlap_minutes("9:00AM-12:00PM", "11:00AM-2:00PM"), Ok(60));
+        assert_eq!(ranges_overlap("9:00AM-12:00PM", "11:00AM-2:00PM"), Ok(true));
+        assert_eq!(overlap_minutes("11:00AM-2:00PM", "9:00AM-12:00PM"), Ok(60));
+        assert_eq!(overlap_minutes("9:00AM-5:00PM", "12:00PM-1:00PM"), Ok(60));
+        assert_eq!(
+            ranges_overlap("9:00AM-12:00PM", "12:00PM-2:00PM"),
+            Ok(false)
+        );
+        assert_eq!(overlap_minutes("9:00AM-12:00PM", "12:00PM-2:00PM"), Ok(0));
+        assert_eq!(ranges_overlap("9:00AM-10:00AM", "1:00PM-2:00PM"), Ok(false));
+        assert_eq!(ranges_overlap("9:00AM-9:00AM", "8:00AM-10:00AM"), Ok(false));
+        assert!(ranges_overlap("9:00AM-12:00PM", "5:00PM-9:00AM").is_err());
+    }
+
+    #[test]
+    fn test_clamp_to_window() {
+        assert_eq!(
+            clamp_to_window("8:00AM-6:00PM", "9:00AM", "5:00PM"),
+            Ok(8.0)
+        );
+        assert_eq!(clamp_to_window("8:00AM-6:00PM", "9:00", "5:00"), Ok(8.0));
+        assert_eq!(
+            clamp_to_window("10:00AM-12:30PM", "9:00AM", "5:00PM"),
+            Ok(2.5)
+        );
+        assert_eq!(
+            clamp_to_window("6:00PM-8:00PM", "9:00AM", "5:00PM"),
+            Ok(0.0)
+        );
+        assert_eq!(
+            clamp_to_window_or_error("6:00PM-8:00PM", "9:00AM", "5:00PM"),
+            Err(TimeError::OutsideWindow {
+                input: "6:00PM-8:00PM".to_string(),
+                window: "9:00AM-5:00PM".to_string(),
+            })
+        );
+        assert_eq!(
+            clamp_to_window_or_error("4:00PM-8:00PM", "9:00AM", "5:00PM"),
+            Ok(1.0)
+        );
+        assert!(clamp_to_window("8:00AM-6:00PM", "5:00PM", "9:00AM").is_err());
+    }
+
+    #[test]
+    fn test_normalize_range() {
+        assert_eq!(
+            normalize_range(" 9:00 am - 5:30 pm "),
+            Ok("09:00AM-05:30PM".to_string())
+        );
+        assert_eq!(
+            normalize_range("9:00-5:30"),
+            Ok("09:00AM-05:30PM".to_string())
+        );
+        assert_eq!(
+            normalize_range("13:00-17:30:15"),
+            Ok("01:00PM-05:30:15PM".to_string())
+        );
+        assert_eq!(
+            normalize_range("noon-5:5p"),
+            Ok("12:00PM-05:05PM".to_string())
+        );
+        for range in [
+            " 9:00 am - 5:30 pm ",
+            "9:00-5:30",
+            "13:00-17:30:15",
+            "noon-5:5p",
+        ] {
+            let once = normalize_range(range).unwrap();
+            assert_eq!(normalize_range(&once), Ok(once.clone()));
+        }
+        assert!(normalize_range("5:30PM-9:00AM").is_err());
+        assert!(normalize_range("9:00AM-5:30").is_err());
+        assert!(normalize_range("9:00  am-5:30pm").is_err());
+    }
+
+    #[test]
+    fn test_midpoint() {
+        assert_eq!(midpoint("9:00AM-5:00PM"), Ok("01:00PM".to_string()));
+        assert_eq!(midpoint("9:00AM-5:30PM"), Ok("01:15PM".to_string()));
+        assert_eq!(midpoint("9:00AM-9:01AM"), Ok("09:01AM".to_string()));
+        assert_eq!(midpoint("9:00AM-9:03AM"), Ok("09:02AM".to_string()));
+        assert_eq!(midpoint("9:00AM-9:00AM"), Ok("09:00AM".to_string()));
+        assert_eq!(midpoint("9:00:20AM-9:00:40AM"), Ok("09:01AM".to_string()));
+        assert_eq!(midpoint("11:59:59PM-11:59:59PM"), Ok("11:59PM".to_string()));
+        assert!(midpoint("5:00PM-9:00AM").is_err());
+    }
+
+    #[test]
+    fn test_normalize_range_with_dots() {
+        assert_eq!(
+            normalize_range("9.00-17.30"),
+            Ok("09:00AM-05:30PM".to_string())
+        );
+        assert_eq!(
+            normalize_range("9.00 am-5.30 pm"),
+            Ok("09:00AM-05:30PM".to_string())
+        );
+        assert_eq!(
+            normalize_range("9.00-5.30"),
+            Ok("09:00AM-05:30PM".to_string())
+        );
+        assert!(normalize_range("9.5-17.5").is_err());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn test_time_range_hash() {
+        use std::collections::HashSet;
+
+        let mut shifts = HashSet::new();
+        shifts.insert("9:00AM-5:00PM".parse::<TimeRange>().unwrap());
+        shifts.insert("09:00AM-05:00PM".parse::<TimeRange>().unwrap());
+        assert_eq!(shifts.len(), 1);
+        shifts.insert("9:00-5:00".parse::<TimeRange>().unwrap());
+        assert_eq!(shifts.len(), 1);
+        shifts.insert("9:00AM-5:30PM".parse::<TimeRange>().unwrap());
+        assert_eq!(shifts.len(), 2);
+
+        let starts: HashSet<TimeOfDay> = shifts.iter().map(TimeRange::start).collect();
+        assert_eq!(starts.len(), 1);
+    }
+
+    #[test]
+    fn test_time_range_new() {
+        let nine = TimeOfDay::parse("9:00AM").unwrap();
+        let half_five = TimeOfDay::parse("5:30PM").unwrap();
+        let range = TimeRange::new(nine, half_five).unwrap();
+        assert_eq!(range.duration_hours(), 8.5);
+        assert_eq!(Ok(range), "9:00AM-5:30PM".parse());
+        assert_eq!(TimeRange::new(nine, nine).unwrap().duration_hours(), 0.0);
+        assert_eq!(
+            TimeRange::new(half_five, nine),
+            Err(TimeError::EndBeforeStart {
+                start: "05:30PM".to_string(),
+                start_interpreted: "05:30PM".to_string(),
+                start_minutes: 1050,
+                end: "09:00AM".to_string(),
+                end_interpreted: "09:00AM".to_string(),
+                end_minutes: 540,
+            })
+        );
+    }
+
+    #[test]
+    fn test_time_range_end_of_day() {
+        let range: TimeRange = "22:00-24:00".parse().unwrap();
+        assert!(range.ends_at_end_of_day());
+        assert_eq!(range.end(), TimeOfDay::MIDNIGHT);
+        assert_eq!(range.span(), TimeSpan::from_minutes(120));
+        assert_eq!(range.to_string(), "22:00-24:00");
+        assert_eq!(range.to_string().parse(), Ok(range));
+        assert_ne!(Ok(range), "22:00-23:59".parse());
+        assert_eq!(
+            normalize_range("22.00-24:00"),
+            Ok("22:00-24:00".to_string())
+        );
+        assert_eq!(midpoint("22:00-24:00"), Ok("11:00PM".to_string()));
+        assert_eq!(overlap_minutes("23:00-24:00", "22:00-24:00"), Ok(60));
+        assert_eq!(overlap_minutes("23:00-24:00", "9:00AM-11:30PM"), Ok(30));
+    }
+}
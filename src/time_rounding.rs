@@ -0,0 +1,111 @@
+use crate::time_span::TimeSpan;
+
+/// How a calculated span is rounded before it is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingMode {
+    /// No rounding; the span is reported to the second.
+    #[default]
+    Exact,
+    /// Rounds to the nearest 15 minutes, with 7m 30s rounding up.
+    NearestQuarter,
+    /// Rounds up to the next 15 minutes, e.g. for billing.
+    CeilQuarter,
+    /// Rounds to the nearest minute, with 30 seconds rounding up.
+    NearestMinute,
+    /// Rounds to the nearest tenth of an hour, 6 minutes, with 3 minutes rounding up,
+    /// as in legal billing.
+    NearestTenth,
+}
+
+impl RoundingMode {
+    /// Rounds `span` according to this mode. A span that would round up past `u32::MAX`
+    /// seconds saturates there, like adding spans does.
+    pub fn apply(self, span: TimeSpan) -> TimeSpan {
+        let seconds = u64::from(span.total_seconds());
+        let rounded = match self {
+            RoundingMode::Exact => seconds,
+            RoundingMode::NearestQuarter => round_to_nearest(seconds, 15 * 60),
+            RoundingMode::CeilQuarter => seconds.div_ceil(15 * 60) * (15 * 60),
+            RoundingMode::NearestMinute => round_to_nearest(seconds, 60),
+            RoundingMode::NearestTenth => round_to_nearest(seconds, 6 * 60),
+        };
+        TimeSpan::from_seconds(u32::try_from(rounded).unwrap_or(u32::MAX))
+    }
+}
+
+/// Rounds in 64 bits, where a `u32` number of seconds cannot overflow.
+fn round_to_nearest(seconds: u64, step: u64) -> u64 {
+    (seconds + step / 2) / step * step
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rounding_mode_apply() {
+        let span = TimeSpan::from_seconds(10 * 60 + 29);
+        assert_eq!(RoundingMode::Exact.apply(span), span);
+        assert_eq!(
+            RoundingMode::NearestQuarter.apply(span),
+            TimeSpan::from_minutes(15)
+        );
+        assert_eq!(
+            RoundingMode::CeilQuarter.apply(span),
+            TimeSpan::from_minutes(15)
+        );
+        assert_eq!(
+            RoundingMode::NearestMinute.apply(span),
+            TimeSpan::from_minutes(10)
+        );
+        assert_eq!(
+            RoundingMode::NearestTenth.apply(span),
+            TimeSpan::from_minutes(12)
+        );
+        assert_eq!(
+            RoundingMode::NearestTenth.apply(TimeSpan::from_seconds(2 * 60 + 59)),
+            TimeSpan::from_minutes(0)
+        );
+        assert_eq!(
+            RoundingMode::NearestTenth.apply(TimeSpan::from_minutes(3)),
+            TimeSpan::from_minutes(6)
+        );
+        assert_eq!(
+            RoundingMode::NearestQuarter.apply(TimeSpan::from_seconds(7 * 60 + 29)),
+            TimeSpan::from_minutes(0)
+        );
+        assert_eq!(
+            RoundingMode::NearestQuarter.apply(TimeSpan::from_seconds(7 * 60 + 30)),
+            TimeSpan::from_minutes(15)
+        );
+        assert_eq!(
+            RoundingMode::CeilQuarter.apply(TimeSpan::from_minutes(30)),
+            TimeSpan::from_minutes(30)
+        );
+        assert_eq!(
+            RoundingMode::CeilQuarter.apply(TimeSpan::from_seconds(0)),
+            TimeSpan::from_seconds(0)
+        );
+    }
+
+    #[test]
+    fn test_rounding_mode_apply_saturates() {
+        let longest = TimeSpan::from_seconds(u32::MAX);
+        for mode in [
+            RoundingMode::Exact,
+            RoundingMode::NearestQuarter,
+            RoundingMode::CeilQuarter,
+            RoundingMode::NearestMinute,
+            RoundingMode::NearestTenth,
+        ] {
+            assert!(mode.apply(longest) <= longest);
+        }
+        assert_eq!(RoundingMode::CeilQuarter.apply(longest), longest);
+        assert_eq!(RoundingMode::NearestQuarter.apply(longest), longest);
+        assert_eq!(RoundingMode::NearestTenth.apply(longest), longest);
+        assert_eq!(
+            RoundingMode::NearestMinute.apply(longest),
+            TimeSpan::from_seconds(u32::MAX / 60 * 60)
+        );
+    }
+}
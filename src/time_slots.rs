@@ -0,0 +1,55 @@
+use crate::time_difference::calculate_range;
+use crate::TimeError;
+
+/// Returns the start of each 15-minute slot in a range, as minutes from midnight,
+/// e.g. "9:00AM-10:00AM" gives 540, 555, 570 and 585. The end itself is not included.
+pub fn quarter_hour_slots(range_str: &str) -> Result<impl Iterator<Item = u32>, TimeError> {
+    slots_with_step(range_str, 15)
+}
+
+/// Returns the start of each `step_minutes` slot in a range, as minutes from midnight,
+/// up to but not including the end. A step of 0 is an error.
+pub fn slots_with_step(
+    range_str: &str,
+    step_minutes: u32,
+) -> Result<impl Iterator<Item = u32>, TimeError> {
+    if step_minutes == 0 {
+        return Err(TimeError::InvalidStep { step_minutes });
+    }
+    let range = calculate_range(range_str)?;
+    let start = range.start.minutes_from_midnight();
+    // A partial final minute still starts a slot, so round the end up.
+    let end = range.end_seconds().div_ceil(60);
+    Ok((start..end).step_by(step_minutes as usize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_quarter_hour_slots() {
+        let slots: Vec<u32> = quarter_hour_slots("9:00AM-10:00AM").unwrap().collect();
+        assert_eq!(slots, vec![540, 555, 570, 585]);
+        let slots: Vec<u32> = quarter_hour_slots("9:00AM-9:20AM").unwrap().collect();
+        assert_eq!(slots, vec![540, 555]);
+        assert_eq!(quarter_hour_slots("9:00AM-9:00AM").unwrap().count(), 0);
+        assert!(quarter_hour_slots("5:00PM-9:00AM").is_err());
+    }
+
+    #[test]
+    fn test_slots_with_step() {
+        let slots: Vec<u32> = slots_with_step("9:00-5:00", 120).unwrap().collect();
+        assert_eq!(slots, vec![540, 660, 780, 900]);
+        let slots: Vec<u32> = slots_with_step("9:00:00AM-9:00:30AM", 1).unwrap().collect();
+        assert_eq!(slots, vec![540]);
+        let slots: Vec<u32> = quarter_hour_slots("23:00-24:00").unwrap().collect();
+        assert_eq!(slots, vec![1380, 1395, 1410, 1425]);
+        assert!(matches!(
+            slots_with_step("9:00-5:00", 0),
+            Err(TimeError::InvalidStep { step_minutes: 0 })
+        ));
+    }
+}
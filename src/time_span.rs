@@ -0,0 +1,269 @@
+use crate::TimeError;
+use alloc::string::ToString;
+
+/// A duration measured in whole seconds, e.g. the length of a time range.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TimeSpan {
+    total_seconds: u32,
+}
+
+impl TimeSpan {
+    /// Creates a span from a total number of minutes. Like adding spans, this saturates at
+    /// `u32::MAX` seconds, reached past 71,582,788 minutes.
+    pub fn from_minutes(total_minutes: u32) -> Self {
+        TimeSpan {
+            total_seconds: total_minutes.saturating_mul(60),
+        }
+    }
+
+    /// Creates a span from a total number of seconds.
+    pub fn from_seconds(total_seconds: u32) -> Self {
+        TimeSpan { total_seconds }
+    }
+
+    /// Returns the whole hours in this span.
+    pub fn hours(&self) -> u32 {
+        self.total_seconds / 3600
+    }
+
+    /// Returns the whole minutes remaining after the whole hours.
+    pub fn minutes(&self) -> u32 {
+        self.total_seconds / 60 % 60
+    }
+
+    /// Returns the seconds remaining after the whole minutes.
+    pub fn seconds(&self) -> u32 {
+        self.total_seconds % 60
+    }
+
+    /// Returns the length of this span in whole minutes.
+    pub fn total_minutes(&self) -> u32 {
+        self.total_seconds / 60
+    }
+
+    /// Returns the length of this span in seconds.
+    pub fn total_seconds(&self) -> u32 {
+        self.total_seconds
+    }
+
+    /// Returns the length of this span in fractional hours.
+    pub fn as_hours_f64(&self) -> f64 {
+        self.total_seconds as f64 / 3600.0
+    }
+
+    /// Returns `self - other`, or `None` if `other` is longer than `self`.
+    pub fn checked_sub(self, other: TimeSpan) -> Option<TimeSpan> {
+        self.total_seconds
+            .checked_sub(other.total_seconds)
+            .map(TimeSpan::from_seconds)
+    }
+}
+
+/// Adds two spans, e.g. the durations of two ranges. The sum saturates at `u32::MAX` seconds.
+impl core::ops::Add for TimeSpan {
+    type Output = TimeSpan;
+
+    fn add(self, other: TimeSpan) -> TimeSpan {
+        TimeSpan::from_seconds(self.total_seconds.saturating_add(other.total_seconds))
+    }
+}
+
+impl core::ops::AddAssign for TimeSpan {
+    fn add_assign(&mut self, other: TimeSpan) {
+        *self = *self + other;
+    }
+}
+
+/// Totals spans from an iterator, e.g. `ranges.iter().map(TimeRange::span).sum()`, saturating
+/// like `Add`. An empty iterator sums to zero.
+impl core::iter::Sum for TimeSpan {
+    fn sum<I: Iterator<Item = TimeSpan>>(iter: I) -> TimeSpan {
+        iter.fold(TimeSpan::from_seconds(0), |total, span| total + span)
+    }
+}
+
+impl<'a> core::iter::Sum<&'a TimeSpan> for TimeSpan {
+    fn sum<I: Iterator<Item = &'a TimeSpan>>(iter: I) -> TimeSpan {
+        iter.copied().sum()
+    }
+}
+
+/// Subtracts a span, e.g. a break. Subtracting a longer span saturates at zero rather than
+/// panicking; use `checked_sub` to detect that case.
+impl core::ops::Sub for TimeSpan {
+    type Output = TimeSpan;
+
+    fn sub(self, other: TimeSpan) -> TimeSpan {
+        TimeSpan::from_seconds(self.total_seconds.saturating_sub(other.total_seconds))
+    }
+}
+
+impl core::ops::SubAssign for TimeSpan {
+    fn sub_assign(&mut self, other: TimeSpan) {
+        *self = *self - other;
+    }
+}
+
+/// Formats as "8h 30m", adding a seconds component ("0h 0m 30s") only when it is non-zero.
+impl core::fmt::Display for TimeSpan {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}h {}m", self.hours(), self.minutes())?;
+        if self.seconds() != 0 {
+            write!(f, " {}s", self.seconds())?;
+        }
+        Ok(())
+    }
+}
+
+impl From<TimeSpan> for core::time::Duration {
+    fn from(span: TimeSpan) -> Self {
+        core::time::Duration::from_secs(u64::from(span.total_seconds))
+    }
+}
+
+/// Parses a compact duration such as "8h", "30m", "8h30m" or "8h 30m" into minutes, e.g. for
+/// `DurationCalculator::break_minutes` ("1h" gives 60). With hours, the minutes must be below
+/// 60; on their own they can be any count, so "90m" gives 90. "h" and "m" may be uppercase.
+pub fn parse_duration(s: &str) -> Result<u32, TimeError> {
+    let invalid = || TimeError::InvalidDuration {
+        input: s.to_string(),
+    };
+    let trimmed = s.trim();
+    let (hours, rest) = match trimmed.find(['h', 'H']) {
+        Some(index) => (Some(&trimmed[..index]), trimmed[index + 1..].trim_start()),
+        None => (None, trimmed),
+    };
+    let minutes = match rest {
+        "" => None,
+        rest => Some(rest.strip_suffix(['m', 'M']).ok_or_else(invalid)?),
+    };
+    let number = |digits: &str| -> Result<u32, TimeError> {
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        digits.parse().map_err(|_| invalid())
+    };
+    match (
+        hours.map(number).transpose()?,
+        minutes.map(number).transpose()?,
+    ) {
+        (None, None) => Err(invalid()),
+        (None, Some(minutes)) => Ok(minutes),
+        (Some(hours), minutes) => {
+            let minutes = minutes.unwrap_or(0);
+            if minutes > 59 {
+                return Err(invalid());
+            }
+            hours
+                .checked_mul(60)
+                .and_then(|hours| hours.checked_add(minutes))
+                .ok_or_else(invalid)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_span_accessors() {
+        let span = TimeSpan::from_minutes(510);
+        assert_eq!(span.hours(), 8);
+        assert_eq!(span.minutes(), 30);
+        assert_eq!(span.seconds(), 0);
+        assert_eq!(span.total_minutes(), 510);
+        assert_eq!(span.total_seconds(), 30600);
+        assert_eq!(span.as_hours_f64(), 8.5);
+        assert_eq!(
+            TimeSpan::from_minutes(u32::MAX),
+            TimeSpan::from_seconds(u32::MAX)
+        );
+        assert_eq!(
+            TimeSpan::from_minutes(71_582_788).total_seconds(),
+            71_582_788 * 60
+        );
+    }
+
+    #[test]
+    fn test_time_span_display() {
+        assert_eq!(TimeSpan::from_minutes(510).to_string(), "8h 30m");
+        assert_eq!(TimeSpan::from_minutes(1439).to_string(), "23h 59m");
+        assert_eq!(TimeSpan::from_minutes(0).to_string(), "0h 0m");
+        assert_eq!(TimeSpan::from_seconds(30).to_string(), "0h 0m 30s");
+        assert_eq!(TimeSpan::from_seconds(3725).to_string(), "1h 2m 5s");
+    }
+
+    #[test]
+    fn test_time_span_into_duration() {
+        assert_eq!(
+            core::time::Duration::from(TimeSpan::from_seconds(3725)),
+            core::time::Duration::from_secs(3725)
+        );
+    }
+
+    #[test]
+    fn test_time_span_arithmetic() {
+        let morning = TimeSpan::from_minutes(120);
+        let afternoon = TimeSpan::from_minutes(240);
+        assert_eq!(morning + afternoon, TimeSpan::from_minutes(360));
+        assert_eq!(afternoon - morning, TimeSpan::from_minutes(120));
+        assert_eq!(morning - afternoon, TimeSpan::from_seconds(0));
+        assert_eq!(afternoon.checked_sub(morning), Some(morning));
+        assert_eq!(morning.checked_sub(afternoon), None);
+        assert_eq!(
+            TimeSpan::from_seconds(u32::MAX) + morning,
+            TimeSpan::from_seconds(u32::MAX)
+        );
+
+        let mut total = morning;
+        total += afternoon;
+        total -= TimeSpan::from_minutes(30);
+        assert_eq!(total, TimeSpan::from_minutes(330));
+    }
+
+    #[test]
+    fn test_time_span_sum() {
+        let spans = alloc::vec![
+            TimeSpan::from_minutes(120),
+            TimeSpan::from_minutes(240),
+            TimeSpan::from_seconds(30),
+        ];
+        let expected = TimeSpan::from_seconds(360 * 60 + 30);
+        assert_eq!(spans.iter().sum::<TimeSpan>(), expected);
+        assert_eq!(spans.into_iter().sum::<TimeSpan>(), expected);
+        assert_eq!(
+            core::iter::empty::<TimeSpan>().sum::<TimeSpan>(),
+            TimeSpan::from_seconds(0)
+        );
+        assert_eq!(
+            [TimeSpan::from_seconds(u32::MAX), TimeSpan::from_minutes(1)]
+                .iter()
+                .sum::<TimeSpan>(),
+            TimeSpan::from_seconds(u32::MAX)
+        );
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("8h"), Ok(480));
+        assert_eq!(parse_duration("30m"), Ok(30));
+        assert_eq!(parse_duration("8h30m"), Ok(510));
+        assert_eq!(parse_duration(" 8h 30m "), Ok(510));
+        assert_eq!(parse_duration("90m"), Ok(90));
+        assert_eq!(parse_duration("1H"), Ok(60));
+        assert_eq!(parse_duration("0h0m"), Ok(0));
+        assert_eq!(
+            parse_duration("8h60m"),
+            Err(TimeError::InvalidDuration {
+                input: "8h60m".to_string()
+            })
+        );
+        for input in [
+            "", "h", "m", "8", "8h30", "30m8h", "-1h", "8 h", "1.5h", "8h30m5s",
+        ] {
+            assert!(parse_duration(input).is_err(), "{}", input);
+        }
+        assert!(parse_duration("99999999h").is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use crate::error::offset_in;
+use crate::time_difference::calculate;
+use crate::{FormatIssue, TimeError};
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// Parses a labeled timesheet such as "Mon:9-5,Tue:9:30-1:00" into each entry's label and
+/// hours, in input order. Each comma-separated entry is split on its first colon into a label,
+/// kept verbatim, and a range. A side of the range given as a bare hour, like "9", means "9:00".
+/// A range that fails is reported as `TimeError::AtIndex` with its entry's position.
+pub fn parse_weekly(input: &str) -> Result<Vec<(String, f64)>, TimeError> {
+    let mut days = Vec::new();
+    for (index, entry) in input.split(',').enumerate() {
+        let entry = entry.trim();
+        if entry.is_empty() {
+            return Err(TimeError::InvalidFormat {
+                input: input.to_string(),
+                issue: FormatIssue::EmptyRange { index },
+                position: offset_in(entry, input),
+            });
+        }
+        let Some((label, range)) = entry.split_once(':').filter(|(label, _)| !label.is_empty())
+        else {
+            return Err(TimeError::InvalidFormat {
+                input: input.to_string(),
+                issue: FormatIssue::MissingLabel { index },
+                position: offset_in(entry, input),
+            });
+        };
+        let hours = calculate(&expand_bare_hours(range)).map_err(|error| TimeError::AtIndex {
+            index,
+            error: Box::new(error),
+        })?;
+        days.push((label.to_string(), hours));
+    }
+    Ok(days)
+}
+
+/// Sums the hours of a labeled timesheet accepted by `parse_weekly`.
+pub fn weekly_total(input: &str) -> Result<f64, TimeError> {
+    Ok(parse_weekly(input)?.iter().map(|(_, hours)| hours).sum())
+}
+
+/// Rewrites each side of a range that is just an hour, e.g. "9-5", as "9:00-5:00".
+fn expand_bare_hours(range: &str) -> String {
+    let expand = |side: &str| {
+        let side = side.trim();
+        if (1..=2).contains(&side.len()) && side.bytes().all(|b| b.is_ascii_digit()) {
+            format!("{}:00", side)
+        } else {
+            side.to_string()
+        }
+    };
+    match range.split_once('-') {
+        Some((start, end)) => format!("{}-{}", expand(start), expand(end)),
+        None => range.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_parse_weekly() {
+        assert_eq!(
+            parse_weekly("Mon:9-5,Tue:9-1"),
+            Ok(vec![("Mon".to_string(), 8.0), ("Tue".to_string(), 4.0)])
+        );
+        assert_eq!(
+            parse_weekly("Wed:9:30AM-5:00PM, thu:10-2:30"),
+            Ok(vec![("Wed".to_string(), 7.5), ("thu".to_string(), 4.5)])
+        );
+        assert_eq!(weekly_total("Mon:9-5,Tue:9-1"), Ok(12.0));
+    }
+
+    #[test]
+    fn test_parse_weekly_errors() {
+        assert!(matches!(
+            parse_weekly("Mon:9-5,9-1"),
+            Err(TimeError::InvalidFormat {
+                issue: FormatIssue::MissingLabel { index: 1 },
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_weekly(":9-5"),
+            Err(TimeError::InvalidFormat {
+                issue: FormatIssue::MissingLabel { index: 0 },
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_weekly("Mon:9-5,,Tue:9-1"),
+            Err(TimeError::InvalidFormat {
+                issue: FormatIssue::EmptyRange { index: 1 },
+                ..
+            })
+        ));
+        assert!(matches!(
+            parse_weekly("Mon:5PM-9AM"),
+            Err(TimeError::AtIndex { index: 0, error })
+                if matches!(*error, TimeError::InvalidFormat { .. })
+        ));
+        let error = parse_weekly("Mon:9-5,Tue:5:00PM-9:00AM").unwrap_err();
+        assert!(matches!(
+            &error,
+            TimeError::AtIndex { index: 1, error }
+                if matches!(**error, TimeError::EndBeforeStart { .. })
+        ));
+        assert!(error.to_string().starts_with("Range at index 1: "));
+    }
+}